@@ -1,4 +1,4 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -50,6 +50,75 @@ pub struct Args {
         help = "Set log level (trace, debug, info, warn, error)"
     )]
     pub log_level: String,
+
+    /// Number of worker threads
+    #[arg(
+        short = 'j',
+        long = "jobs",
+        value_name = "N",
+        help = "Lex and parse files on N threads (default: number of CPUs)"
+    )]
+    pub jobs: Option<usize>,
+
+    /// Synthesis mode
+    #[arg(
+        long = "synthesis",
+        help = "Drop translate_off/translate_on regions before parsing"
+    )]
+    pub synthesis: bool,
+
+    /// Token dump format
+    #[arg(
+        long = "token-format",
+        value_name = "FORMAT",
+        value_enum,
+        default_value_t = TokenFormat::Text,
+        help = "Format of out/lexer/tokens dumps"
+    )]
+    pub token_format: TokenFormat,
+
+    /// Token statistics report
+    #[arg(
+        long = "stats",
+        value_name = "FORMAT",
+        value_enum,
+        help = "Write per-file and aggregate token statistics to out/lexer/stats.<ext>"
+    )]
+    pub stats: Option<StatsFormat>,
+
+    /// Lexer configuration file
+    #[arg(
+        long = "lexer-config",
+        value_name = "FILE",
+        help = "TOML lexer configuration (plugins, token config, debug mode)"
+    )]
+    pub lexer_config: Option<PathBuf>,
+
+    /// Shared-library lexer plugins to load at startup
+    #[cfg(feature = "dynamic-plugins")]
+    #[arg(
+        long = "lexer-plugin",
+        value_name = "PATH",
+        help = "Load a lexer plugin from a shared library (repeatable)"
+    )]
+    pub lexer_plugins: Vec<PathBuf>,
+}
+
+/// Token dump formats selectable on the command line
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenFormat {
+    /// `Kind: 'text'` lines
+    Text,
+    /// JSON Lines with kind, category, span, line, column and text
+    Jsonl,
+}
+
+/// Token statistics report formats
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsFormat {
+    Text,
+    Json,
+    Csv,
 }
 
 impl Args {
@@ -62,6 +131,10 @@ impl Args {
             return Err("Either input files (-i) or input directory (--dir) must be specified".to_string());
         }
 
+        if self.jobs == Some(0) {
+            return Err("Number of jobs (-j) must be at least 1".to_string());
+        }
+
         // Validate input files exist
         for file in &self.input_files {
             if !file.exists() {
@@ -69,6 +142,21 @@ impl Args {
             }
         }
 
+        // Validate lexer config exists
+        if let Some(ref config) = self.lexer_config {
+            if !config.is_file() {
+                return Err(format!("Lexer config does not exist: {}", config.display()));
+            }
+        }
+
+        // Validate lexer plugin libraries exist
+        #[cfg(feature = "dynamic-plugins")]
+        for plugin in &self.lexer_plugins {
+            if !plugin.is_file() {
+                return Err(format!("Lexer plugin does not exist: {}", plugin.display()));
+            }
+        }
+
         // Validate input directory exists
         if let Some(ref dir) = self.input_dir {
             if !dir.exists() || !dir.is_dir() {
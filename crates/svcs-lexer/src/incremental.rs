@@ -0,0 +1,180 @@
+//! Incremental re-lexing of edited buffers.
+//!
+//! Only the region around an edit is re-tokenized. Lexing restarts at the last
+//! token before the edit that is followed by whitespace, or earlier at a
+//! `` `pragma `` on the edited line, and stops as soon as a
+//! new token starts where a (shifted) old token from after the edit started: from
+//! that boundary on, the text and therefore the token stream are unchanged.
+
+use crate::lexer::{SpannedToken, SystemVerilogLexer};
+use crate::{LexError, Token};
+use std::ops::Range;
+
+/// Replacement of `range` in the old text by `text`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+impl TextEdit {
+    pub fn new(range: Range<usize>, text: impl Into<String>) -> Self {
+        Self { range, text: text.into() }
+    }
+
+    /// Apply the edit to the old text
+    pub fn apply(&self, old: &str) -> String {
+        let mut new = String::with_capacity(old.len() + self.text.len());
+        new.push_str(&old[..self.range.start]);
+        new.push_str(&self.text);
+        new.push_str(&old[self.range.end..]);
+        new
+    }
+
+    /// Change in text length caused by the edit
+    fn delta(&self) -> isize {
+        self.text.len() as isize - self.range.len() as isize
+    }
+}
+
+impl SystemVerilogLexer<'_> {
+    /// Update `old_tokens` for `edit`. The lexer must have been built over the
+    /// edited text, and `old_tokens` must be the complete stream of the old text.
+    pub fn relex(&mut self, old_tokens: &[SpannedToken], edit: &TextEdit) -> Result<Vec<SpannedToken>, LexError> {
+        let source = self.source();
+        let keep = reusable_prefix(source, old_tokens, edit);
+        let restart = keep.checked_sub(1).map_or(0, |last| old_tokens[last].1.end);
+
+        // Old tokens starting after the edit are resynchronization candidates
+        let delta = edit.delta();
+        let mut candidate = old_tokens.partition_point(|(_, span)| span.start < edit.range.end);
+
+        let mut tokens = old_tokens[..keep].to_vec();
+        self.seek(restart);
+        for token in self.by_ref() {
+            let (token, span) = token?;
+
+            while candidate < old_tokens.len() && shift(old_tokens[candidate].1.start, delta) < span.start {
+                candidate += 1;
+            }
+            if let Some((old_token, old_span)) = old_tokens.get(candidate) {
+                if shift(old_span.start, delta) == span.start && *old_token == token {
+                    tracing::debug!(
+                        "Re-lexed {} tokens, resynchronized at byte {}",
+                        tokens.len() - keep,
+                        span.start
+                    );
+                    tokens.extend(old_tokens[candidate..].iter().map(|(token, span)| {
+                        (token.clone(), shift(span.start, delta)..shift(span.end, delta))
+                    }));
+                    return Ok(tokens);
+                }
+            }
+            tokens.push((token, span));
+        }
+        Ok(tokens)
+    }
+}
+
+/// Number of leading old tokens that are unaffected by the edit. A token is kept
+/// only if it ends before the edit and is followed by whitespace: a token touching
+/// its successor (or an unterminated `/*`) could merge into a longer match.
+/// A `` `pragma `` reads the rest of its line to decide whether it opens a
+/// protected envelope, so none on the edited line is kept.
+fn reusable_prefix(source: &str, old_tokens: &[SpannedToken], edit: &TextEdit) -> usize {
+    let mut keep = old_tokens.partition_point(|(_, span)| span.end < edit.range.start);
+    while keep > 0 {
+        let end = old_tokens[keep - 1].1.end;
+        if source.as_bytes().get(end).is_some_and(|b| b.is_ascii_whitespace()) {
+            break;
+        }
+        keep -= 1;
+    }
+
+    let line_start = source[..edit.range.start].rfind('\n').map_or(0, |newline| newline + 1);
+    let on_line = old_tokens[..keep].iter().rev().take_while(|(_, span)| span.start >= line_start).count();
+    if let Some(pragma) = old_tokens[keep - on_line..keep]
+        .iter()
+        .position(|(token, span)| *token == Token::PreprocessorDirective && &source[span.clone()] == "`pragma")
+    {
+        keep -= on_line - pragma;
+    }
+    keep
+}
+
+fn shift(pos: usize, delta: isize) -> usize {
+    (pos as isize + delta) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_default_lexer;
+    use proptest::prelude::*;
+
+    fn lex(text: &str) -> Result<Vec<SpannedToken>, LexError> {
+        create_default_lexer(text, "test.sv".to_string()).unwrap().tokenize()
+    }
+
+    fn relex(old: &str, edit: &TextEdit) -> Result<Vec<SpannedToken>, LexError> {
+        let old_tokens = lex(old).unwrap();
+        let new = edit.apply(old);
+        create_default_lexer(&new, "test.sv".to_string()).unwrap().relex(&old_tokens, edit)
+    }
+
+    #[test]
+    fn test_relex_merges_and_splits_tokens() {
+        let old = "assign ab = c ; wire w;";
+        for edit in [
+            TextEdit::new(9..9, "xy"),          // extend an identifier
+            TextEdit::new(8..9, "a b"),         // split an identifier
+            TextEdit::new(0..0, "/* "),         // open a comment over everything
+            TextEdit::new(14..15, "; /*x*/ ;"), // insert a comment
+        ] {
+            let new = edit.apply(old);
+            assert_eq!(
+                format!("{:?}", relex(old, &edit)),
+                format!("{:?}", lex(&new)),
+                "edit {:?}",
+                edit
+            );
+        }
+    }
+
+    #[test]
+    fn test_relex_pragma_opening_envelope() {
+        let old = "`pragma x\nwire a;\n";
+        let edit = TextEdit::new(8..9, "protect begin_protected\nZZZ\n`pragma protect end_protected");
+        let new = edit.apply(old);
+        let full = lex(&new).unwrap();
+        assert_eq!(full[0], (Token::ProtectedEnvelope, 0..65));
+        assert_eq!(relex(old, &edit).unwrap(), full);
+    }
+
+    const FRAGMENTS: &[&str] = &[
+        " ", "\n", "module", "endmodule", "assign", "a", "b1", "_x", "$display", "1", "4'b10",
+        "3.5", "(", ")", "[", "]", ";", ",", ".", ":", "::", "=", "==", "<", "<=", "<<", "*",
+        "/", "/*", "*/", "//", "\"s\"", "`define", "#", "@", "`pragma", "protect begin_protected",
+        "protect end_protected", "`pragma protect begin_protected\n", "\n`pragma protect end_protected",
+    ];
+
+    fn text() -> impl Strategy<Value = String> {
+        prop::collection::vec(prop::sample::select(FRAGMENTS), 0..40).prop_map(|parts| parts.concat())
+    }
+
+    proptest! {
+        // Old texts with an unterminated protected envelope do not lex and are rejected
+        #![proptest_config(ProptestConfig { max_global_rejects: 100_000, ..ProptestConfig::default() })]
+
+        #[test]
+        fn prop_relex_matches_full_lex(old in text(), replacement in text(), a in any::<usize>(), b in any::<usize>()) {
+            prop_assume!(lex(&old).is_ok());
+            let (start, end) = (a % (old.len() + 1), b % (old.len() + 1));
+            let edit = TextEdit::new(start.min(end)..start.max(end), replacement);
+
+            let full = lex(&edit.apply(&old));
+            let incremental = relex(&old, &edit);
+            prop_assert_eq!(format!("{:?}", incremental), format!("{:?}", full));
+        }
+    }
+}
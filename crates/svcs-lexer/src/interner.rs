@@ -0,0 +1,116 @@
+//! String interning for identifier lexemes.
+//!
+//! An [`Interner`] maps identifier and system-identifier text to compact [`Symbol`]
+//! ids. It is thread-safe and meant to be shared by every file in a compilation, so
+//! later stages can compare names by id instead of by string.
+//!
+//! Symbols are numbered in the order texts are first interned. When files are
+//! lexed in parallel that order depends on thread scheduling, so the same name
+//! can get a different id from run to run: compare symbols within one interner,
+//! and never write ids to output or persist them.
+
+use crate::lexer::SpannedToken;
+use crate::Token;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Interned string id; only meaningful together with the interner that produced it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    pub fn as_u32(self) -> u32 {
+        self.0
+    }
+}
+
+#[derive(Default)]
+struct InternerInner {
+    map: HashMap<Arc<str>, Symbol>,
+    strings: Vec<Arc<str>>,
+}
+
+#[derive(Default)]
+pub struct Interner {
+    inner: RwLock<InternerInner>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Intern `text`, returning the existing symbol if it was seen before
+    pub fn intern(&self, text: &str) -> Symbol {
+        if let Some(symbol) = self.get(text) {
+            return symbol;
+        }
+        let mut inner = self.inner.write().unwrap();
+        if let Some(&symbol) = inner.map.get(text) {
+            return symbol;
+        }
+        let symbol = Symbol(inner.strings.len() as u32);
+        let text: Arc<str> = Arc::from(text);
+        inner.strings.push(text.clone());
+        inner.map.insert(text, symbol);
+        symbol
+    }
+
+    /// Symbol for `text` if it has been interned
+    pub fn get(&self, text: &str) -> Option<Symbol> {
+        self.inner.read().unwrap().map.get(text).copied()
+    }
+
+    /// Text of a symbol. Panics if the symbol came from another interner.
+    pub fn resolve(&self, symbol: Symbol) -> Arc<str> {
+        self.inner.read().unwrap().strings[symbol.0 as usize].clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.read().unwrap().strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Intern the lexeme of an identifier or system identifier token
+    pub fn intern_token(&self, token: &Token, text: &str) -> Option<Symbol> {
+        match token {
+            Token::Identifier | Token::SystemIdentifier => Some(self.intern(text)),
+            _ => None,
+        }
+    }
+
+    /// Symbols for a token stream, aligned with `tokens`
+    pub fn intern_tokens(&self, source: &str, tokens: &[SpannedToken]) -> Vec<Option<Symbol>> {
+        tokens.iter()
+            .map(|(token, span)| self.intern_token(token, &source[span.clone()]))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_default_lexer;
+
+    #[test]
+    fn test_identifiers_share_symbols_across_files() {
+        let interner = Interner::new();
+        let a = "module top; wire clk; $display(clk); endmodule";
+        let b = "module sub; wire clk; endmodule";
+        let a_tokens = create_default_lexer(a, "a.sv".to_string()).unwrap().tokenize().unwrap();
+        let b_tokens = create_default_lexer(b, "b.sv".to_string()).unwrap().tokenize().unwrap();
+
+        let a_symbols = interner.intern_tokens(a, &a_tokens);
+        let b_symbols = interner.intern_tokens(b, &b_tokens);
+
+        assert_eq!(a_symbols[0], None);
+        assert_eq!(a_symbols[4], b_symbols[4]);
+        assert_ne!(a_symbols[1], b_symbols[1]);
+        assert_eq!(&*interner.resolve(a_symbols[6].unwrap()), "$display");
+        assert_eq!(interner.len(), 4);
+    }
+}
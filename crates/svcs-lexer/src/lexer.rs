@@ -1,23 +1,43 @@
-use crate::{Token, LexError, LexerRegistry};
+use crate::{opens_protected_envelope, protected_envelope, Token, LexError, LexerRegistry};
 use crate::traits::{LexerPlugin, TokenConfig};
-use crate::utils::TokenStats;
+use crate::utils::{LexerConfig, TokenStats};
 use logos::Logos;
+use std::collections::{HashMap, VecDeque};
 use std::ops::Range;
 
+/// A token with its byte span in the input
+pub type SpannedToken = (Token, Range<usize>);
+
+/// SystemVerilog lexer. Tokens can be collected at once with [`tokenize`](Self::tokenize)
+/// or pulled lazily through the `Iterator` implementation, with
+/// [`peek`](Self::peek)/[`peek_nth`](Self::peek_nth) for lookahead.
 pub struct SystemVerilogLexer<'input> {
     lexer: logos::Lexer<'input, Token>,
+    source: &'input str,
+    /// Byte offset of the logos lexer's slice within `source`
+    offset: usize,
     file_path: String,
     registry: LexerRegistry,
     config: TokenConfig,
+    debug_mode: bool,
+    /// Tokens lexed ahead of the iterator by `peek_nth`
+    lookahead: VecDeque<Result<SpannedToken, LexError>>,
+    /// Set once an error has been produced; the stream ends after it
+    failed: bool,
 }
 
 impl<'input> SystemVerilogLexer<'input> {
     pub fn new(input: &'input str, file_path: String, registry: LexerRegistry) -> Self {
         Self {
             lexer: Token::lexer(input),
+            source: input,
+            offset: 0,
             file_path,
             registry,
             config: TokenConfig::default(),
+            debug_mode: false,
+            lookahead: VecDeque::new(),
+            failed: false,
         }
     }
     
@@ -26,110 +46,368 @@ impl<'input> SystemVerilogLexer<'input> {
         self
     }
     
-    pub fn tokenize(&mut self) -> Result<Vec<(Token, Range<usize>)>, LexError> {
-        let mut tokens = Vec::new();
+    /// Log every token as it is produced
+    pub fn with_debug_mode(mut self, debug_mode: bool) -> Self {
+        self.debug_mode = debug_mode;
+        self
+    }
+    
+    pub fn tokenize(&mut self) -> Result<Vec<SpannedToken>, LexError> {
+        let tokens = self.by_ref().collect::<Result<Vec<_>, _>>()?;
+        tracing::debug!("Tokenized {} tokens from {}", tokens.len(), self.file_path);
+        Ok(tokens)
+    }
+    
+    pub fn tokenize_with_stats(&mut self) -> Result<(Vec<SpannedToken>, TokenStats), LexError> {
+        let tokens = self.tokenize()?;
+        let stats = TokenStats::from_source(self.file_path.clone(), self.source, &tokens, &self.registry);
+        Ok((tokens, stats))
+    }
+    
+    /// Look at the next token without consuming it
+    pub fn peek(&mut self) -> Option<Result<&SpannedToken, &LexError>> {
+        self.peek_nth(0)
+    }
+    
+    /// Look `n` tokens ahead (0 = next token) without consuming anything
+    pub fn peek_nth(&mut self, n: usize) -> Option<Result<&SpannedToken, &LexError>> {
+        while self.lookahead.len() <= n {
+            let token = self.lex_next()?;
+            self.lookahead.push_back(token);
+        }
+        self.lookahead.get(n).map(Result::as_ref)
+    }
+    
+    pub fn registry(&self) -> &LexerRegistry {
+        &self.registry
+    }
+    
+    /// The full input this lexer was built over
+    pub fn source(&self) -> &'input str {
+        self.source
+    }
+    
+    /// Restart lexing at byte offset `pos`, which must be a token boundary.
+    /// Any lookahead and error state is discarded.
+    pub fn seek(&mut self, pos: usize) {
+        self.lexer = Token::lexer(&self.source[pos..]);
+        self.offset = pos;
+        self.lookahead.clear();
+        self.failed = false;
+    }
+    
+    /// Lex the next token from the input, applying keyword remapping, disabled
+    /// tokens and recognizer validation
+    fn lex_next(&mut self) -> Option<Result<SpannedToken, LexError>> {
+        if self.failed {
+            return None;
+        }
         
-        while let Some(token) = self.lexer.next() {
+        loop {
+            let token = self.lexer.next()?;
             let span = self.lexer.span();
+            let span = span.start + self.offset..span.end + self.offset;
             let text = self.lexer.slice();
             
-            match token {
-                Ok(tok) => {
-                    // Skip disabled tokens
-                    if self.config.disabled_tokens.contains(&tok) {
-                        continue;
-                    }
-                    
-                    // Validate token with registered recognizers
-                    if let Some(category) = self.registry.get_token_category(&tok) {
-                        if let Some(recognizer) = self.registry.get_recognizer(&category) {
-                            if !recognizer.validate_token(&tok, text) {
-                                return Err(LexError::InvalidToken {
-                                    file: self.file_path.clone(),
-                                    span: span.clone(),
-                                    text: text.to_string(),
-                                });
-                            }
-                        }
-                    }
-                    
-                    tokens.push((tok, span));
+            let Ok(mut tok) = token else {
+                return Some(Err(self.invalid_token(span, text)));
+            };
+
+            // A protected envelope is one opaque token up to its end_protected
+            if tok == Token::PreprocessorDirective && text == "`pragma"
+                && opens_protected_envelope(self.lexer.remainder())
+            {
+                if !protected_envelope(&mut self.lexer) {
+                    return Some(Err(self.invalid_token(span, text)));
                 }
-                Err(_) => {
-                    return Err(LexError::InvalidToken {
-                        file: self.file_path.clone(),
-                        span: span.clone(),
-                        text: text.to_string(),
-                    });
+                let end = self.lexer.span().end + self.offset;
+                return Some(Ok((Token::ProtectedEnvelope, span.start..end)));
+            }
+            
+            // Identifiers may be remapped to keywords by config or plugins
+            if tok == Token::Identifier {
+                if let Some(keyword) = self.config.custom_keywords.get(text)
+                    .or_else(|| self.registry.keyword_token(text))
+                {
+                    tok = keyword.clone();
                 }
             }
+            
+            // Skip disabled tokens
+            if self.config.disabled_tokens.contains(&tok) {
+                continue;
+            }
+            
+            // Validate token with registered recognizers
+            if let Some(category) = self.registry.get_token_category(&tok) {
+                if let Some(recognizer) = self.registry.get_recognizer(&category) {
+                    if !recognizer.validate_token(&tok, text) {
+                        return Some(Err(self.invalid_token(span, text)));
+                    }
+                }
+            }
+            
+            if self.debug_mode {
+                tracing::debug!("{}: {:?} {:?} '{}'", self.file_path, tok, span, text);
+            }
+            return Some(Ok((tok, span)));
         }
-        
-        tracing::debug!("Tokenized {} tokens from {}", tokens.len(), self.file_path);
-        Ok(tokens)
     }
     
-    pub fn tokenize_with_stats(&mut self) -> Result<(Vec<(Token, Range<usize>)>, TokenStats), LexError> {
-        let tokens = self.tokenize()?;
-        let stats = TokenStats::from_tokens(&tokens, &self.registry);
-        Ok((tokens, stats))
+    fn invalid_token(&mut self, span: Range<usize>, text: &str) -> LexError {
+        self.failed = true;
+        LexError::InvalidToken {
+            file: self.file_path.clone(),
+            span,
+            text: text.to_string(),
+        }
     }
+}
+
+impl Iterator for SystemVerilogLexer<'_> {
+    type Item = Result<SpannedToken, LexError>;
     
-    pub fn registry(&self) -> &LexerRegistry {
-        &self.registry
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.lookahead.pop_front() {
+            Some(token) => Some(token),
+            None => self.lex_next(),
+        }
     }
 }
 
 /// Builder for creating SystemVerilog lexers with plugins
 pub struct LexerBuilder {
-    registry: LexerRegistry,
+    plugins: Vec<Box<dyn LexerPlugin>>,
     config: TokenConfig,
+    debug_mode: bool,
 }
 
 impl LexerBuilder {
     pub fn new() -> Self {
         Self {
-            registry: LexerRegistry::new(),
+            plugins: Vec::new(),
             config: TokenConfig::default(),
+            debug_mode: false,
         }
     }
     
-    /// Add a plugin to the lexer
+    /// Add a plugin to the lexer. Plugins may be added in any order; they are
+    /// registered in dependency order when the lexer is built.
     pub fn with_plugin(mut self, plugin: Box<dyn LexerPlugin>) -> Self {
-        if let Err(e) = self.registry.register_plugin(plugin) {
-            tracing::error!("Failed to register plugin: {}", e);
-        }
+        self.plugins.push(plugin);
         self
     }
     
     /// Add all default plugins
     pub fn with_default_plugins(mut self) -> Self {
-        use crate::plugins::*;
-        
-        self = self.with_plugin(Box::new(ModulePlugin::default()));
-        self = self.with_plugin(Box::new(InterfacePlugin::default()));
-        self = self.with_plugin(Box::new(DataTypePlugin::default()));
-        self = self.with_plugin(Box::new(ControlPlugin::default()));
-        self = self.with_plugin(Box::new(OperatorPlugin::default()));
-        self = self.with_plugin(Box::new(GeneralPlugin::default()));
-        
+        for name in crate::plugins::BUILTIN_PLUGINS {
+            if let Some(plugin) = crate::plugins::builtin_plugin(name) {
+                self = self.with_plugin(plugin);
+            }
+        }
         self
     }
     
+    /// Apply a lexer configuration: the built-in plugins listed in `enabled_plugins`
+    /// and not in `disabled_plugins`, the token configuration and debug mode.
+    /// Keyword plugins are loaded separately with [`LexerConfig::load_keyword_plugins`].
+    pub fn with_lexer_config(mut self, config: &LexerConfig) -> Result<Self, LexError> {
+        for name in &config.enabled_plugins {
+            if config.disabled_plugins.contains(name) {
+                continue;
+            }
+            if !crate::plugins::BUILTIN_PLUGINS.contains(&name.as_str()) {
+                return Err(LexError::RegistryError {
+                    message: format!("Unknown built-in plugin '{}' in lexer config", name),
+                });
+            }
+            match crate::plugins::builtin_plugin(name) {
+                Some(plugin) => self = self.with_plugin(plugin),
+                None => tracing::warn!("Plugin '{}' is enabled in config but not compiled in", name),
+            }
+        }
+        self.config = config.token_config.clone();
+        self.debug_mode = config.debug_mode;
+        Ok(self)
+    }
+    
     /// Set token configuration
     pub fn with_config(mut self, config: TokenConfig) -> Self {
         self.config = config;
         self
     }
     
-    /// Build the lexer
-    pub fn build<'input>(self, input: &'input str, file_path: String) -> SystemVerilogLexer<'input> {
-        SystemVerilogLexer::new(input, file_path, self.registry).with_config(self.config)
+    /// Build the lexer, registering plugins in dependency order
+    pub fn build(self, input: &str, file_path: String) -> Result<SystemVerilogLexer<'_>, LexError> {
+        let mut registry = LexerRegistry::new();
+        for plugin in sort_by_dependencies(self.plugins)? {
+            registry.register_plugin(plugin)
+                .map_err(|message| LexError::RegistryError { message })?;
+        }
+        Ok(SystemVerilogLexer::new(input, file_path, registry)
+            .with_config(self.config)
+            .with_debug_mode(self.debug_mode))
     }
 }
 
+/// Topologically sort plugins so every plugin follows its dependencies.
+/// Plugins without an ordering constraint keep the order they were added in.
+fn sort_by_dependencies(plugins: Vec<Box<dyn LexerPlugin>>) -> Result<Vec<Box<dyn LexerPlugin>>, LexError> {
+    let mut index = HashMap::new();
+    for (i, plugin) in plugins.iter().enumerate() {
+        if index.insert(plugin.name(), i).is_some() {
+            return Err(LexError::RegistryError {
+                message: format!("Plugin '{}' added more than once", plugin.name()),
+            });
+        }
+    }
+    
+    let mut in_degree = vec![0usize; plugins.len()];
+    let mut dependencies: Vec<Vec<usize>> = vec![Vec::new(); plugins.len()];
+    let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); plugins.len()];
+    for (i, plugin) in plugins.iter().enumerate() {
+        for dep in plugin.dependencies() {
+            let Some(&d) = index.get(dep) else {
+                return Err(LexError::RegistryError {
+                    message: format!("Plugin '{}' depends on '{}' which was not added", plugin.name(), dep),
+                });
+            };
+            in_degree[i] += 1;
+            dependencies[i].push(d);
+            dependents[d].push(i);
+        }
+    }
+    
+    let mut order = Vec::with_capacity(plugins.len());
+    let mut ready: Vec<usize> = (0..plugins.len()).filter(|&i| in_degree[i] == 0).collect();
+    while let Some(&next) = ready.iter().min() {
+        ready.retain(|&i| i != next);
+        order.push(next);
+        for &dependent in &dependents[next] {
+            in_degree[dependent] -= 1;
+            if in_degree[dependent] == 0 {
+                ready.push(dependent);
+            }
+        }
+    }
+    
+    if order.len() < plugins.len() {
+        // Every unsorted plugin waits on another unsorted one, so following those
+        // dependencies must revisit a plugin; the path from there on is the cycle
+        let mut path = Vec::new();
+        let mut current = (0..plugins.len()).find(|&i| in_degree[i] > 0).unwrap();
+        while !path.contains(&current) {
+            path.push(current);
+            current = dependencies[current].iter().copied().find(|&d| in_degree[d] > 0).unwrap();
+        }
+        let start = path.iter().position(|&i| i == current).unwrap();
+        let cycle: Vec<&str> = path[start..].iter().map(|&i| plugins[i].name()).collect();
+        return Err(LexError::RegistryError {
+            message: format!("Dependency cycle between plugins: {}", cycle.join(", ")),
+        });
+    }
+    
+    let mut slots: Vec<Option<Box<dyn LexerPlugin>>> = plugins.into_iter().map(Some).collect();
+    Ok(order.into_iter().filter_map(|i| slots[i].take()).collect())
+}
+
 impl Default for LexerBuilder {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::TokenRecognizer;
+
+    struct DepPlugin {
+        name: &'static str,
+        deps: Vec<&'static str>,
+    }
+
+    impl LexerPlugin for DepPlugin {
+        fn name(&self) -> &'static str { self.name }
+        fn version(&self) -> &'static str { "0.0.1" }
+        fn description(&self) -> &'static str { "dependency test plugin" }
+        fn recognizers(&self) -> Vec<Box<dyn TokenRecognizer>> { vec![] }
+        fn initialize(&mut self) -> Result<(), Box<dyn std::error::Error>> { Ok(()) }
+        fn dependencies(&self) -> Vec<&'static str> { self.deps.clone() }
+    }
+
+    fn plugin(name: &'static str, deps: &[&'static str]) -> Box<dyn LexerPlugin> {
+        Box::new(DepPlugin { name, deps: deps.to_vec() })
+    }
+
+    #[test]
+    fn test_plugins_registered_in_dependency_order() {
+        let lexer = LexerBuilder::new()
+            .with_plugin(plugin("c", &["b"]))
+            .with_plugin(plugin("b", &["a"]))
+            .with_plugin(plugin("a", &[]))
+            .build("", "test.sv".to_string())
+            .unwrap();
+        assert_eq!(lexer.registry().stats().total_plugins, 3);
+    }
+
+    #[test]
+    fn test_streaming_with_lookahead() {
+        let input = "assign a = b;";
+        let mut lexer = crate::create_default_lexer(input, "test.sv".to_string()).unwrap();
+
+        assert_eq!(lexer.peek_nth(2).unwrap().unwrap().0, Token::Assign);
+        assert_eq!(lexer.peek().unwrap().unwrap().0, Token::AssignKeyword);
+        let first = lexer.next().unwrap().unwrap();
+        assert_eq!(first, (Token::AssignKeyword, 0..6));
+
+        let rest: Vec<_> = lexer.by_ref().take(4).map(|t| t.unwrap().0).collect();
+        assert_eq!(rest, vec![Token::Identifier, Token::Assign, Token::Identifier, Token::Semicolon]);
+
+        // The stream ends after the first error
+        let mut lexer = crate::create_default_lexer("a \\ b", "test.sv".to_string()).unwrap();
+        assert!(lexer.next().unwrap().is_ok());
+        assert!(lexer.next().unwrap().is_err());
+        assert!(lexer.next().is_none());
+    }
+
+    #[test]
+    #[cfg(all(feature = "module-plugin", feature = "general-plugin"))]
+    fn test_builder_from_lexer_config() {
+        let config = LexerConfig {
+            enabled_plugins: vec!["general".to_string(), "module".to_string()],
+            disabled_plugins: vec!["module".to_string()],
+            ..LexerConfig::default()
+        };
+        let lexer = LexerBuilder::new()
+            .with_lexer_config(&config)
+            .unwrap()
+            .build("", "test.sv".to_string())
+            .unwrap();
+        assert_eq!(lexer.registry().list_categories(), vec!["general".to_string()]);
+
+        let unknown = LexerConfig {
+            enabled_plugins: vec!["bogus".to_string()],
+            ..LexerConfig::default()
+        };
+        assert!(LexerBuilder::new().with_lexer_config(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_missing_dependency_and_cycle_reported() {
+        let missing = LexerBuilder::new()
+            .with_plugin(plugin("a", &["nope"]))
+            .build("", "test.sv".to_string());
+        assert!(matches!(missing, Err(LexError::RegistryError { ref message }) if message.contains("nope")));
+
+        // Plugins that merely depend on the cycle are not part of it
+        let cycle = LexerBuilder::new()
+            .with_plugin(plugin("a", &[]))
+            .with_plugin(plugin("d", &["b"]))
+            .with_plugin(plugin("b", &["c"]))
+            .with_plugin(plugin("c", &["b"]))
+            .build("", "test.sv".to_string());
+        assert!(matches!(cycle, Err(LexError::RegistryError { ref message })
+            if message == "Dependency cycle between plugins: b, c"));
+    }
+}
@@ -6,23 +6,29 @@ pub mod error;
 pub mod traits;
 pub mod registry;
 pub mod lexer;
+pub mod incremental;
+pub mod interner;
+pub mod pragma;
 pub mod plugins;
 pub mod utils;
 
 // Re-export core types
 pub use error::LexError;
 pub use traits::{TokenRecognizer, LexerPlugin, TokenConfig};
-pub use registry::LexerRegistry;
-pub use lexer::{SystemVerilogLexer, LexerBuilder};
+pub use registry::{LexerRegistry, TokenConflict};
+pub use lexer::{SystemVerilogLexer, LexerBuilder, SpannedToken};
+pub use incremental::TextEdit;
+pub use interner::{Interner, Symbol};
+pub use pragma::{Pragma, PragmaFilter, PragmaKind, Pragmas};
 
 use logos::Logos;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Logos, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Logos, Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 #[logos(skip r"[ \t\r\n\f]+")]
 #[logos(skip r"//[^\n]*")]
-#[logos(skip r"/\*([^*]|\*[^/])*\*/")]
+#[logos(skip r"/\*[^*]*\*+([^/*][^*]*\*+)*/")]
 pub enum Token {
     // Module tokens
     #[token("module")]
@@ -33,6 +39,10 @@ pub enum Token {
     Package,
     #[token("endpackage")]
     EndPackage,
+    #[token("program")]
+    Program,
+    #[token("endprogram")]
+    EndProgram,
     #[token("import")]
     Import,
     #[token("export")]
@@ -87,16 +97,82 @@ pub enum Token {
     Real,
     #[token("string")]
     String,
+    #[token("shortint")]
+    ShortInt,
+    #[token("longint")]
+    LongInt,
+    #[token("time")]
+    Time,
+    #[token("shortreal")]
+    ShortReal,
+    #[token("realtime")]
+    RealTime,
+    #[token("chandle")]
+    Chandle,
+    #[token("event")]
+    Event,
     #[token("wire")]
     Wire,
     #[token("reg")]
     Reg,
+    #[token("tri")]
+    Tri,
+    #[token("triand")]
+    TriAnd,
+    #[token("trior")]
+    TriOr,
+    #[token("tri0")]
+    Tri0,
+    #[token("tri1")]
+    Tri1,
+    #[token("trireg")]
+    TriReg,
+    #[token("wand")]
+    WAnd,
+    #[token("wor")]
+    WOr,
+    #[token("supply0")]
+    Supply0,
+    #[token("supply1")]
+    Supply1,
+    #[token("uwire")]
+    UWire,
+    #[token("vectored")]
+    Vectored,
+    #[token("scalared")]
+    Scalared,
+    #[token("strong0")]
+    Strong0,
+    #[token("strong1")]
+    Strong1,
+    #[token("pull0")]
+    Pull0,
+    #[token("pull1")]
+    Pull1,
+    #[token("weak0")]
+    Weak0,
+    #[token("weak1")]
+    Weak1,
+    #[token("highz0")]
+    HighZ0,
+    #[token("highz1")]
+    HighZ1,
+    #[token("small")]
+    Small,
+    #[token("medium")]
+    Medium,
+    #[token("large")]
+    Large,
     #[token("input")]
     Input,
     #[token("output")]
     Output,
     #[token("inout")]
     Inout,
+    #[token("ref")]
+    Ref,
+    #[token("var")]
+    Var,
     #[token("signed")]
     Signed,
     #[token("unsigned")]
@@ -115,6 +191,8 @@ pub enum Token {
     Parameter,
     #[token("localparam")]
     LocalParam,
+    #[token("type")]
+    Type,
     #[token("const")]
     Const,
     #[token("static")]
@@ -133,10 +211,28 @@ pub enum Token {
     AlwaysLatch,
     #[token("initial")]
     Initial,
+    #[token("final")]
+    Final,
+    #[token("posedge")]
+    PosEdge,
+    #[token("negedge")]
+    NegEdge,
+    #[token("edge")]
+    Edge,
+    #[token("or")]
+    Or,
+    #[token("iff")]
+    Iff,
     #[token("if")]
     If,
     #[token("else")]
     Else,
+    #[token("unique")]
+    Unique,
+    #[token("unique0")]
+    Unique0,
+    #[token("priority")]
+    Priority,
     #[token("case")]
     Case,
     #[token("casex")]
@@ -225,6 +321,27 @@ pub enum Token {
     BitwiseXor,
     #[token("~")]
     BitwiseNot,
+    #[token("~&")]
+    ReductionNand,
+    #[token("~|")]
+    ReductionNor,
+    #[token("~^")]
+    #[token("^~")]
+    BitwiseXnor,
+    #[token("==?")]
+    WildcardEqual,
+    #[token("!=?")]
+    WildcardNotEqual,
+    #[token("->")]
+    Implication,
+    #[token("<->")]
+    Equivalence,
+    #[token("+:")]
+    IndexedPlus,
+    #[token("-:")]
+    IndexedMinus,
+    #[token("inside")]
+    Inside,
     #[token("<<")]
     LeftShift,
     #[token(">>")]
@@ -267,8 +384,11 @@ pub enum Token {
     Identifier,
     #[regex(r"\$[a-zA-Z_][a-zA-Z0-9_$]*")]
     SystemIdentifier,
-    #[regex(r"[0-9]*'[bdhoDBDHO][0-9a-fA-FxzXZ_]*")]
+    #[regex(r"[0-9]*'[sS]?[bdhoDBDHO][0-9a-fA-FxzXZ_?]*")]
     SizedNumber,
+    /// `'0`, `'1`, `'x` or `'z`, filling the whole width of its context
+    #[regex(r"'[01xXzZ]")]
+    UnbasedUnsizedLiteral,
     #[regex(r"[0-9]+")]
     UnsizedNumber,
     #[regex(r"[0-9]*\.[0-9]+([eE][+-]?[0-9]+)?")]
@@ -303,11 +423,50 @@ pub enum Token {
     Hash,
     #[token("@")]
     At,
+    /// Cast and assignment pattern prefix: `int'(x)`, `'{a, b}`
+    #[token("'")]
+    Apostrophe,
+    /// Unbounded range end or queue dimension
+    #[token("$")]
+    Dollar,
     #[regex(r"`[a-zA-Z_][a-zA-Z0-9_]*")]
     PreprocessorDirective,
+    /// A whole `` `pragma protect begin_protected `` ... `` end_protected `` envelope,
+    /// encoded payload included; produced from a `` `pragma `` directive by the lexer
+    ProtectedEnvelope,
+    /// Keyword contributed by a plugin; never produced by the base lexer
+    CustomKeyword,
     Error,
 }
 
+/// Whether `` `pragma `` followed by `rest` opens a protected envelope
+pub(crate) fn opens_protected_envelope(rest: &str) -> bool {
+    pragma_words(rest) == ["protect", "begin_protected"]
+}
+
+/// Extend a `` `pragma `` match that opens a protected envelope to the end of its
+/// `end_protected` directive. An envelope without one is a lexical error.
+pub(crate) fn protected_envelope(lex: &mut logos::Lexer<Token>) -> bool {
+    let rest = lex.remainder();
+    let mut search = 0;
+    while let Some(found) = rest[search..].find("`pragma") {
+        let after = search + found + "`pragma".len();
+        if pragma_words(&rest[after..]) == ["protect", "end_protected"] {
+            let end = rest[after..].find("end_protected").unwrap() + "end_protected".len();
+            lex.bump(after + end);
+            return true;
+        }
+        search = after;
+    }
+    false
+}
+
+/// First two words of a `` `pragma `` line
+fn pragma_words(rest: &str) -> Vec<&str> {
+    let line = rest.split('\n').next().unwrap_or("");
+    line.split_whitespace().take(2).collect()
+}
+
 impl fmt::Display for Token {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}", self)
@@ -315,17 +474,19 @@ impl fmt::Display for Token {
 }
 
 /// Easy-to-use API for creating a lexer with default plugins
-pub fn create_default_lexer(input: &str, file_path: String) -> SystemVerilogLexer<'_> {
+pub fn create_default_lexer(input: &str, file_path: String) -> Result<SystemVerilogLexer<'_>, LexError> {
     LexerBuilder::new()
         .with_default_plugins()
         .build(input, file_path)
 }
 
 /// Easy-to-use API for creating a minimal lexer
-pub fn create_minimal_lexer(input: &str, file_path: String) -> SystemVerilogLexer<'_> {
-    LexerBuilder::new()
-        .with_plugin(Box::new(plugins::GeneralPlugin::default()))
-        .build(input, file_path)
+pub fn create_minimal_lexer(input: &str, file_path: String) -> Result<SystemVerilogLexer<'_>, LexError> {
+    let mut builder = LexerBuilder::new();
+    if let Some(plugin) = plugins::builtin_plugin("general") {
+        builder = builder.with_plugin(plugin);
+    }
+    builder.build(input, file_path)
 }
 
 #[cfg(test)]
@@ -335,11 +496,47 @@ mod tests {
     #[test]
     fn test_basic_module() {
         let input = "module test_module(input clk, output data); assign data = clk; endmodule";
-        let mut lexer = create_default_lexer(input, "test.sv".to_string());
+        let mut lexer = create_default_lexer(input, "test.sv".to_string()).unwrap();
         let tokens = lexer.tokenize().unwrap();
         
         assert_eq!(tokens[0].0, Token::Module);
         assert_eq!(tokens[1].0, Token::Identifier);
         assert_eq!(tokens[2].0, Token::LeftParen);
     }
+
+    #[test]
+    fn test_protected_envelope_is_one_token() {
+        let input = "module ip(input a);\n\
+            `pragma protect begin_protected\n\
+            `pragma protect encoding = (enctype = \"base64\")\n\
+            `pragma protect data_block\n\
+            aGVsbG8s+/d29y'bGQ=\n\
+            `pragma protect end_protected\n\
+            endmodule";
+        let tokens = create_default_lexer(input, "ip.sv".to_string()).unwrap().tokenize().unwrap();
+        let kinds: Vec<&Token> = tokens.iter().map(|(token, _)| token).collect();
+        assert_eq!(kinds[7..], [&Token::ProtectedEnvelope, &Token::EndModule]);
+        assert!(input[tokens[7].1.clone()].ends_with("`pragma protect end_protected"));
+
+        let unterminated = "`pragma protect begin_protected\nAAAA";
+        assert!(create_default_lexer(unterminated, "ip.sv".to_string()).unwrap().tokenize().is_err());
+    }
+
+    #[test]
+    fn test_expression_operators() {
+        let kinds = |input: &str| -> Vec<Token> {
+            let tokens = create_default_lexer(input, "e.sv".to_string()).unwrap().tokenize().unwrap();
+            tokens.into_iter().map(|(token, _)| token).collect()
+        };
+        assert_eq!(kinds("a[i+:4]"), [Token::Identifier, Token::LeftBracket, Token::Identifier,
+            Token::IndexedPlus, Token::UnsizedNumber, Token::RightBracket]);
+        assert_eq!(kinds("~&a ^~ b"), [Token::ReductionNand, Token::Identifier, Token::BitwiseXnor, Token::Identifier]);
+        assert_eq!(kinds("8'(x)"), [Token::UnsizedNumber, Token::Apostrophe, Token::LeftParen,
+            Token::Identifier, Token::RightParen]);
+        assert_eq!(kinds("'{'0, 4'sb1?}"), [Token::Apostrophe, Token::LeftBrace, Token::UnbasedUnsizedLiteral,
+            Token::Comma, Token::SizedNumber, Token::RightBrace]);
+        // A partial longer operator falls back to the shorter one
+        assert_eq!(kinds("a<-b"), [Token::Identifier, Token::LessThan, Token::Minus, Token::Identifier]);
+        assert_eq!(kinds("a inside {[1:$]}")[1..3], [Token::Inside, Token::LeftBrace]);
+    }
 }
@@ -19,13 +19,17 @@ impl Default for ControlTokenRecognizer {
                 Token::End, Token::Fork, Token::Join, Token::JoinAny,
                 Token::JoinNone, Token::Wait, Token::Disable,
                 Token::Break, Token::Continue, Token::Return,
+                Token::Final, Token::PosEdge, Token::NegEdge, Token::Edge,
+                Token::Or, Token::Iff, Token::Unique, Token::Unique0, Token::Priority,
             ],
             keywords: vec![
                 "always", "always_comb", "always_ff", "always_latch", "initial",
                 "if", "else", "case", "casex", "casez", "endcase", "default",
                 "for", "foreach", "while", "do", "repeat", "forever",
                 "begin", "end", "fork", "join", "join_any", "join_none",
-                "wait", "disable", "break", "continue", "return"
+                "wait", "disable", "break", "continue", "return",
+                "final", "posedge", "negedge", "edge", "or", "iff", "unique",
+                "unique0", "priority"
             ],
         }
     }
@@ -49,16 +53,11 @@ impl TokenRecognizer for ControlTokenRecognizer {
     }
 }
 
+#[derive(Default)]
 pub struct ControlPlugin {
     recognizer: Option<ControlTokenRecognizer>,
 }
 
-impl Default for ControlPlugin {
-    fn default() -> Self {
-        Self { recognizer: None }
-    }
-}
-
 impl LexerPlugin for ControlPlugin {
     fn name(&self) -> &'static str {
         "SystemVerilog Control Flow Plugin"
@@ -16,14 +16,26 @@ impl Default for DataTypeTokenRecognizer {
                 Token::Reg, Token::Input, Token::Output, Token::Inout,
                 Token::Signed, Token::Unsigned, Token::Packed,
                 Token::Struct, Token::Union, Token::Enum, Token::Typedef,
-                Token::Parameter, Token::LocalParam, Token::Const,
-                Token::Static, Token::Automatic,
+                Token::Parameter, Token::LocalParam, Token::Type, Token::Const,
+                Token::Static, Token::Automatic, Token::Tri, Token::TriAnd,
+                Token::TriOr, Token::Tri0, Token::Tri1, Token::TriReg, Token::WAnd,
+                Token::WOr, Token::Supply0, Token::Supply1, Token::UWire, Token::Ref,
+                Token::Var, Token::ShortInt, Token::LongInt, Token::Time,
+                Token::ShortReal, Token::RealTime, Token::Chandle, Token::Event,
+                Token::Vectored, Token::Scalared, Token::Strong0, Token::Strong1,
+                Token::Pull0, Token::Pull1, Token::Weak0, Token::Weak1, Token::HighZ0,
+                Token::HighZ1, Token::Small, Token::Medium, Token::Large,
             ],
             keywords: vec![
                 "logic", "bit", "byte", "int", "integer", "real", "string",
                 "wire", "reg", "input", "output", "inout", "signed", "unsigned",
                 "packed", "struct", "union", "enum", "typedef", "parameter",
-                "localparam", "const", "static", "automatic"
+                "localparam", "type", "const", "static", "automatic", "tri", "triand",
+                "trior", "tri0", "tri1", "trireg", "wand", "wor", "supply0",
+                "supply1", "uwire", "ref", "var", "shortint", "longint", "time",
+                "shortreal", "realtime", "chandle", "event", "vectored", "scalared",
+                "strong0", "strong1", "pull0", "pull1", "weak0", "weak1", "highz0",
+                "highz1", "small", "medium", "large"
             ],
         }
     }
@@ -47,16 +59,11 @@ impl TokenRecognizer for DataTypeTokenRecognizer {
     }
 }
 
+#[derive(Default)]
 pub struct DataTypePlugin {
     recognizer: Option<DataTypeTokenRecognizer>,
 }
 
-impl Default for DataTypePlugin {
-    fn default() -> Self {
-        Self { recognizer: None }
-    }
-}
-
 impl LexerPlugin for DataTypePlugin {
     fn name(&self) -> &'static str {
         "SystemVerilog DataType Plugin"
@@ -0,0 +1,311 @@
+//! Runtime-loadable lexer plugins from shared libraries.
+//!
+//! A plugin library is a `cdylib` crate that depends on `svcs-lexer` and exports its
+//! keyword set through [`declare_lexer_plugin!`](crate::declare_lexer_plugin):
+//!
+//! ```ignore
+//! svcs_lexer::declare_lexer_plugin! {
+//!     name: "Acme Pragma Plugin",
+//!     version: "0.1.0",
+//!     description: "Acme proprietary pragma keywords",
+//!     category: "acme",
+//!     priority: 60,
+//!     keywords: {
+//!         "acme_keep" => "CustomKeyword",
+//!         "acme_async" => "CustomKeyword",
+//!     }
+//! }
+//! ```
+//!
+//! Only `#[repr(C)]` data crosses the library boundary, so plugins do not need to be
+//! built with the same compiler as the host.
+
+use super::keyword_table::{leak, KeywordTable, KeywordTableRecognizer};
+use crate::traits::{LexerPlugin, TokenRecognizer};
+use crate::{LexError, Token};
+use serde::de::IntoDeserializer;
+use serde::Deserialize;
+use std::ffi::{c_char, CStr};
+use std::path::Path;
+use std::sync::Arc;
+
+/// ABI version of [`PluginDeclaration`]; bumped on any layout change
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Symbol every plugin library must export
+pub const PLUGIN_ENTRY_SYMBOL: &str = "svcs_lexer_plugin";
+
+/// Version of svcs-lexer the host was built from
+pub const HOST_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[doc(hidden)]
+pub const HOST_VERSION_NUL: &str = concat!(env!("CARGO_PKG_VERSION"), "\0");
+
+/// Signature of the exported entry point
+pub type PluginEntryFn = unsafe extern "C" fn() -> *const PluginDeclaration;
+
+/// A keyword and the name of the `Token` variant it lexes to
+#[repr(C)]
+pub struct KeywordEntry {
+    pub keyword: *const c_char,
+    pub token: *const c_char,
+}
+
+/// Plugin description exported by a shared library. All strings are NUL-terminated
+/// and must stay valid for the lifetime of the library.
+#[repr(C)]
+pub struct PluginDeclaration {
+    pub abi_version: u32,
+    /// svcs-lexer version the plugin was compiled against
+    pub host_version: *const c_char,
+    pub name: *const c_char,
+    pub version: *const c_char,
+    pub description: *const c_char,
+    pub category: *const c_char,
+    pub priority: u8,
+    pub keywords: *const KeywordEntry,
+    pub keyword_count: usize,
+}
+
+// Declarations are immutable statics made of pointers to string literals
+unsafe impl Sync for KeywordEntry {}
+unsafe impl Sync for PluginDeclaration {}
+
+/// Export a [`PluginDeclaration`] from a `cdylib` crate
+#[macro_export]
+macro_rules! declare_lexer_plugin {
+    (
+        name: $name:literal,
+        version: $version:literal,
+        description: $description:literal,
+        category: $category:literal,
+        priority: $priority:expr,
+        keywords: { $($keyword:literal => $token:literal),* $(,)? } $(,)?
+    ) => {
+        #[no_mangle]
+        pub extern "C" fn svcs_lexer_plugin() -> *const $crate::plugins::PluginDeclaration {
+            static KEYWORDS: &[$crate::plugins::KeywordEntry] = &[
+                $($crate::plugins::KeywordEntry {
+                    keyword: concat!($keyword, "\0").as_ptr() as *const ::std::ffi::c_char,
+                    token: concat!($token, "\0").as_ptr() as *const ::std::ffi::c_char,
+                }),*
+            ];
+            static DECLARATION: $crate::plugins::PluginDeclaration = $crate::plugins::PluginDeclaration {
+                abi_version: $crate::plugins::PLUGIN_ABI_VERSION,
+                host_version: $crate::plugins::HOST_VERSION_NUL.as_ptr() as *const ::std::ffi::c_char,
+                name: concat!($name, "\0").as_ptr() as *const ::std::ffi::c_char,
+                version: concat!($version, "\0").as_ptr() as *const ::std::ffi::c_char,
+                description: concat!($description, "\0").as_ptr() as *const ::std::ffi::c_char,
+                category: concat!($category, "\0").as_ptr() as *const ::std::ffi::c_char,
+                priority: $priority,
+                keywords: KEYWORDS.as_ptr(),
+                keyword_count: KEYWORDS.len(),
+            };
+            &DECLARATION
+        }
+    };
+}
+
+/// A lexer plugin loaded from a shared library. Cloning is cheap, so one loaded
+/// library can back the lexers of many files.
+#[derive(Clone)]
+pub struct DynamicPlugin {
+    /// Contents copied out of the declaration into host memory
+    table: Arc<KeywordTable>,
+    /// Keeps the shared library mapped while the plugin is in use
+    _library: Option<Arc<libloading::Library>>,
+    recognizer: Option<KeywordTableRecognizer>,
+}
+
+impl DynamicPlugin {
+    /// Load a plugin from a shared library exporting [`PLUGIN_ENTRY_SYMBOL`]
+    pub fn load(path: &Path) -> Result<Self, LexError> {
+        let plugin_error = |message: String| LexError::PluginError {
+            message: format!("{}: {}", path.display(), message),
+        };
+
+        // SAFETY: loading a library runs its initializers; plugin libraries are
+        // trusted code explicitly selected by the user.
+        let library = unsafe { libloading::Library::new(path) }
+            .map_err(|e| plugin_error(e.to_string()))?;
+        let declaration = unsafe {
+            let entry = library.get::<PluginEntryFn>(PLUGIN_ENTRY_SYMBOL.as_bytes())
+                .map_err(|e| plugin_error(e.to_string()))?;
+            entry()
+        };
+
+        // SAFETY: the declaration points into the library, which is still loaded
+        let table = unsafe { read_declaration(declaration) }.map_err(plugin_error)?;
+        tracing::info!("Loaded lexer plugin '{}' v{} from {}", table.name, table.version, path.display());
+        Ok(Self { table: Arc::new(table), _library: Some(Arc::new(library)), recognizer: None })
+    }
+
+    /// Build a plugin from a declaration linked into the current process
+    ///
+    /// # Safety
+    /// `declaration` must point to a valid [`PluginDeclaration`] whose strings outlive the call.
+    pub unsafe fn from_declaration(declaration: *const PluginDeclaration) -> Result<Self, LexError> {
+        let table = read_declaration(declaration).map_err(|message| LexError::PluginError { message })?;
+        Ok(Self { table: Arc::new(table), _library: None, recognizer: None })
+    }
+}
+
+/// Check version compatibility and copy a declaration into host-owned memory
+unsafe fn read_declaration(declaration: *const PluginDeclaration) -> Result<KeywordTable, String> {
+    let declaration = declaration.as_ref().ok_or("plugin entry point returned null")?;
+
+    if declaration.abi_version != PLUGIN_ABI_VERSION {
+        return Err(format!(
+            "plugin ABI version {} does not match host ABI version {}",
+            declaration.abi_version, PLUGIN_ABI_VERSION
+        ));
+    }
+    let plugin_host = read_str(declaration.host_version)?;
+    if !versions_compatible(plugin_host, HOST_VERSION) {
+        return Err(format!(
+            "plugin was built against svcs-lexer {} but host is {}",
+            plugin_host, HOST_VERSION
+        ));
+    }
+
+    let mut keywords = Vec::with_capacity(declaration.keyword_count);
+    if declaration.keyword_count > 0 {
+        let entries = std::slice::from_raw_parts(declaration.keywords, declaration.keyword_count);
+        for entry in entries {
+            let keyword = read_str(entry.keyword)?;
+            let token_name = read_str(entry.token)?;
+            let token = token_from_name(token_name)
+                .ok_or_else(|| format!("keyword '{}' maps to unknown token '{}'", keyword, token_name))?;
+            keywords.push((leak(keyword), token));
+        }
+    }
+
+    Ok(KeywordTable {
+        name: leak(read_str(declaration.name)?),
+        version: leak(read_str(declaration.version)?),
+        description: leak(read_str(declaration.description)?),
+        category: leak(read_str(declaration.category)?),
+        priority: declaration.priority,
+        keywords,
+        validation: None,
+    })
+}
+
+unsafe fn read_str<'a>(ptr: *const c_char) -> Result<&'a str, String> {
+    if ptr.is_null() {
+        return Err("plugin declaration contains a null string".to_string());
+    }
+    CStr::from_ptr(ptr).to_str().map_err(|e| format!("plugin string is not UTF-8: {}", e))
+}
+
+/// 0.x releases are only compatible within the same minor version, 1.0+ within a major
+fn versions_compatible(plugin: &str, host: &str) -> bool {
+    let parts = |v: &str| -> Vec<u64> { v.split('.').take(2).filter_map(|p| p.parse().ok()).collect() };
+    match (parts(plugin).as_slice(), parts(host).as_slice()) {
+        ([0, plugin_minor], [0, host_minor]) => plugin_minor == host_minor,
+        ([plugin_major, _], [host_major, _]) => plugin_major == host_major,
+        _ => false,
+    }
+}
+
+/// Resolve a `Token` variant from its name, e.g. `"CustomKeyword"`
+pub fn token_from_name(name: &str) -> Option<Token> {
+    Token::deserialize(name.into_deserializer())
+        .map_err(|_: serde::de::value::Error| ())
+        .ok()
+}
+
+impl LexerPlugin for DynamicPlugin {
+    fn name(&self) -> &'static str {
+        self.table.name
+    }
+
+    fn version(&self) -> &'static str {
+        self.table.version
+    }
+
+    fn description(&self) -> &'static str {
+        self.table.description
+    }
+
+    fn recognizers(&self) -> Vec<Box<dyn TokenRecognizer>> {
+        if let Some(ref recognizer) = self.recognizer {
+            vec![Box::new(recognizer.clone())]
+        } else {
+            vec![]
+        }
+    }
+
+    fn initialize(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.recognizer = Some(KeywordTableRecognizer::new(self.table.clone()));
+        tracing::info!("Initialized {} v{}", self.name(), self.version());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LexerBuilder;
+
+    mod vendor {
+        crate::declare_lexer_plugin! {
+            name: "Vendor Pragma Plugin",
+            version: "0.2.0",
+            description: "Vendor pragma keywords",
+            category: "vendor",
+            priority: 70,
+            keywords: {
+                "vendor_keep" => "CustomKeyword",
+                "vendor_async" => "CustomKeyword",
+            }
+        }
+    }
+
+    #[test]
+    fn test_declared_plugin_remaps_keywords() {
+        let plugin = unsafe { DynamicPlugin::from_declaration(vendor::svcs_lexer_plugin()) }.unwrap();
+        let mut lexer = LexerBuilder::new()
+            .with_default_plugins()
+            .with_plugin(Box::new(plugin))
+            .build("vendor_keep wire w;", "test.sv".to_string())
+            .unwrap();
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[0].0, Token::CustomKeyword);
+        assert_eq!(tokens[1].0, Token::Wire);
+        assert_eq!(lexer.registry().get_token_category(&Token::CustomKeyword).as_deref(), Some("vendor"));
+    }
+
+    #[test]
+    fn test_incompatible_declarations_rejected() {
+        let text = |s: &'static str| s.as_ptr() as *const c_char;
+        let declaration = |abi_version, host_version| PluginDeclaration {
+            abi_version,
+            host_version: text(host_version),
+            name: text("Old Plugin\0"),
+            version: text("0.1.0\0"),
+            description: text("\0"),
+            category: text("old\0"),
+            priority: 50,
+            keywords: std::ptr::null(),
+            keyword_count: 0,
+        };
+        let error = |declaration: PluginDeclaration| match unsafe { DynamicPlugin::from_declaration(&declaration) } {
+            Err(LexError::PluginError { message }) => message,
+            _ => panic!("declaration accepted"),
+        };
+        assert!(error(declaration(PLUGIN_ABI_VERSION + 1, HOST_VERSION_NUL)).contains("ABI version"));
+        assert!(error(declaration(PLUGIN_ABI_VERSION, "99.0.0\0")).contains("built against svcs-lexer 99.0.0"));
+    }
+
+    #[test]
+    fn test_version_compatibility() {
+        assert!(versions_compatible("0.1.3", "0.1.0"));
+        assert!(!versions_compatible("0.2.0", "0.1.0"));
+        assert!(versions_compatible("1.4.0", "1.0.2"));
+        assert!(!versions_compatible("garbage", "0.1.0"));
+        assert_eq!(token_from_name("Wire"), Some(Token::Wire));
+        assert_eq!(token_from_name("NotAToken"), None);
+    }
+}
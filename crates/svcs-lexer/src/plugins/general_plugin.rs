@@ -17,7 +17,8 @@ impl Default for GeneralTokenRecognizer {
                 Token::RightBracket, Token::LeftBrace, Token::RightBrace,
                 Token::Dot, Token::Comma, Token::Semicolon, Token::Colon,
                 Token::Scope, Token::Question, Token::Hash, Token::At,
-                Token::PreprocessorDirective,
+                Token::PreprocessorDirective, Token::ProtectedEnvelope,
+                Token::UnbasedUnsizedLiteral, Token::Apostrophe, Token::Dollar,
             ],
             keywords: vec![], // General tokens are mostly symbols
         }
@@ -42,16 +43,11 @@ impl TokenRecognizer for GeneralTokenRecognizer {
     }
 }
 
+#[derive(Default)]
 pub struct GeneralPlugin {
     recognizer: Option<GeneralTokenRecognizer>,
 }
 
-impl Default for GeneralPlugin {
-    fn default() -> Self {
-        Self { recognizer: None }
-    }
-}
-
 impl LexerPlugin for GeneralPlugin {
     fn name(&self) -> &'static str {
         "SystemVerilog General Plugin"
@@ -40,16 +40,11 @@ impl TokenRecognizer for InterfaceTokenRecognizer {
     }
 }
 
+#[derive(Default)]
 pub struct InterfacePlugin {
     recognizer: Option<InterfaceTokenRecognizer>,
 }
 
-impl Default for InterfacePlugin {
-    fn default() -> Self {
-        Self { recognizer: None }
-    }
-}
-
 impl LexerPlugin for InterfacePlugin {
     fn name(&self) -> &'static str {
         "SystemVerilog Interface Plugin"
@@ -0,0 +1,166 @@
+//! Declarative keyword plugins defined in TOML files.
+//!
+//! ```toml
+//! name = "Acme Pragma Plugin"
+//! version = "0.1.0"
+//! description = "Acme proprietary pragma keywords"
+//! category = "acme"
+//! priority = 60
+//! validation = "^acme_[a-z_]+$"
+//!
+//! [keywords]
+//! acme_keep = "CustomKeyword"
+//! acme_async = "CustomKeyword"
+//! ```
+
+use super::keyword_table::{leak, KeywordTable, KeywordTableRecognizer};
+use crate::traits::{TokenRecognizer, LexerPlugin};
+use crate::{LexError, Token};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// On-disk definition of a keyword plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeywordPluginDef {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    pub category: String,
+    #[serde(default = "default_priority")]
+    pub priority: u8,
+    /// Keyword lexeme to the name of the `Token` variant it lexes to
+    pub keywords: BTreeMap<String, Token>,
+    /// Regex every lexeme of this plugin's tokens must match
+    #[serde(default)]
+    pub validation: Option<String>,
+}
+
+fn default_priority() -> u8 {
+    50
+}
+
+/// A keyword plugin built from a [`KeywordPluginDef`]. Cloning is cheap, so one
+/// definition can back the lexers of many files.
+#[derive(Clone)]
+pub struct KeywordPlugin {
+    table: Arc<KeywordTable>,
+    recognizer: Option<KeywordTableRecognizer>,
+}
+
+impl KeywordPlugin {
+    pub fn from_def(def: KeywordPluginDef) -> Result<Self, LexError> {
+        let validation = def.validation.as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| LexError::PluginError {
+                message: format!("Invalid validation regex in plugin '{}': {}", def.name, e),
+            })?;
+
+        let table = KeywordTable {
+            name: leak(&def.name),
+            version: leak(&def.version),
+            description: leak(&def.description),
+            category: leak(&def.category),
+            priority: def.priority,
+            keywords: def.keywords.into_iter().map(|(keyword, token)| (leak(&keyword), token)).collect(),
+            validation,
+        };
+        Ok(Self { table: Arc::new(table), recognizer: None })
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, LexError> {
+        let def: KeywordPluginDef = toml::from_str(content).map_err(|e| LexError::PluginError {
+            message: format!("Invalid keyword plugin definition: {}", e),
+        })?;
+        Self::from_def(def)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, LexError> {
+        let content = std::fs::read_to_string(path).map_err(|e| LexError::PluginError {
+            message: format!("Failed to read keyword plugin {}: {}", path.display(), e),
+        })?;
+        Self::from_toml(&content).map_err(|e| LexError::PluginError {
+            message: format!("{}: {}", path.display(), e),
+        })
+    }
+}
+
+impl LexerPlugin for KeywordPlugin {
+    fn name(&self) -> &'static str {
+        self.table.name
+    }
+
+    fn version(&self) -> &'static str {
+        self.table.version
+    }
+
+    fn description(&self) -> &'static str {
+        self.table.description
+    }
+
+    fn recognizers(&self) -> Vec<Box<dyn TokenRecognizer>> {
+        if let Some(ref recognizer) = self.recognizer {
+            vec![Box::new(recognizer.clone())]
+        } else {
+            vec![]
+        }
+    }
+
+    fn initialize(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.recognizer = Some(KeywordTableRecognizer::new(self.table.clone()));
+        tracing::info!("Initialized {} v{}", self.name(), self.version());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LexerBuilder;
+
+    const ACME: &str = r#"
+        name = "Acme Pragma Plugin"
+        version = "0.1.0"
+        category = "acme"
+        priority = 60
+        validation = "^acme_[a-z]+$"
+
+        [keywords]
+        acme_keep = "CustomKeyword"
+        acme_Bad = "CustomKeyword"
+    "#;
+
+    #[test]
+    fn test_keyword_plugin_from_toml() {
+        let plugin = KeywordPlugin::from_toml(ACME).unwrap();
+        let mut lexer = LexerBuilder::new()
+            .with_default_plugins()
+            .with_plugin(Box::new(plugin.clone()))
+            .build("acme_keep wire w;", "test.sv".to_string())
+            .unwrap();
+        let tokens = lexer.tokenize().unwrap();
+        assert_eq!(tokens[0].0, Token::CustomKeyword);
+        assert_eq!(lexer.registry().get_token_category(&Token::CustomKeyword).as_deref(), Some("acme"));
+
+        // Lexemes failing the validation regex are rejected
+        let mut lexer = LexerBuilder::new()
+            .with_default_plugins()
+            .with_plugin(Box::new(plugin))
+            .build("acme_Bad", "test.sv".to_string())
+            .unwrap();
+        assert!(matches!(lexer.tokenize(), Err(LexError::InvalidToken { .. })));
+    }
+
+    #[test]
+    fn test_invalid_definitions_rejected() {
+        let unknown_token = ACME.replace("acme_keep = \"CustomKeyword\"", "acme_keep = \"Bogus\"");
+        assert!(KeywordPlugin::from_toml(&unknown_token).is_err());
+
+        let bad_regex = ACME.replace("^acme_[a-z]+$", "(");
+        assert!(matches!(KeywordPlugin::from_toml(&bad_regex), Err(LexError::PluginError { .. })));
+    }
+}
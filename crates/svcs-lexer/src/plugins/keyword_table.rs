@@ -0,0 +1,75 @@
+//! Keyword tables shared by plugins that are defined as data rather than code:
+//! TOML [keyword plugins](super::keyword_plugin) and shared-library
+//! [dynamic plugins](super::dynamic_plugin).
+
+use crate::traits::TokenRecognizer;
+use crate::Token;
+use regex::Regex;
+use std::sync::Arc;
+
+/// Plugin metadata and keyword-to-token table. Strings are leaked once when the
+/// plugin is loaded because the plugin traits hand out `&'static str`.
+pub(crate) struct KeywordTable {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub description: &'static str,
+    pub category: &'static str,
+    pub priority: u8,
+    pub keywords: Vec<(&'static str, Token)>,
+    /// Regex every lexeme of the table's tokens must match
+    pub validation: Option<Regex>,
+}
+
+/// Recognizes the tokens of a [`KeywordTable`]
+#[derive(Clone)]
+pub struct KeywordTableRecognizer {
+    table: Arc<KeywordTable>,
+}
+
+impl KeywordTableRecognizer {
+    pub(crate) fn new(table: Arc<KeywordTable>) -> Self {
+        Self { table }
+    }
+}
+
+impl TokenRecognizer for KeywordTableRecognizer {
+    fn tokens(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = Vec::new();
+        for (_, token) in &self.table.keywords {
+            if !tokens.contains(token) {
+                tokens.push(token.clone());
+            }
+        }
+        tokens
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        self.table.keywords.iter().map(|(keyword, _)| *keyword).collect()
+    }
+
+    fn handles_token(&self, token: &Token) -> bool {
+        self.table.keywords.iter().any(|(_, t)| t == token)
+    }
+
+    fn category_name(&self) -> &'static str {
+        self.table.category
+    }
+
+    fn priority(&self) -> u8 {
+        self.table.priority
+    }
+
+    fn validate_token(&self, _token: &Token, text: &str) -> bool {
+        self.table.validation.as_ref().is_none_or(|regex| regex.is_match(text))
+    }
+
+    fn keyword_tokens(&self) -> Vec<(String, Token)> {
+        self.table.keywords.iter()
+            .map(|(keyword, token)| (keyword.to_string(), token.clone()))
+            .collect()
+    }
+}
+
+pub(crate) fn leak(s: &str) -> &'static str {
+    Box::leak(s.to_string().into_boxed_str())
+}
@@ -1,15 +1,60 @@
 //! Plugin implementations for different token categories
 
+#[cfg(feature = "module-plugin")]
 pub mod module_plugin;
+#[cfg(feature = "interface-plugin")]
 pub mod interface_plugin;
+#[cfg(feature = "datatype-plugin")]
 pub mod datatype_plugin;
+#[cfg(feature = "control-plugin")]
 pub mod control_plugin;
+#[cfg(feature = "operator-plugin")]
 pub mod operator_plugin;
+#[cfg(feature = "general-plugin")]
 pub mod general_plugin;
+pub mod keyword_table;
+pub mod keyword_plugin;
+#[cfg(feature = "dynamic-plugins")]
+pub mod dynamic_plugin;
 
+#[cfg(feature = "module-plugin")]
 pub use module_plugin::*;
+#[cfg(feature = "interface-plugin")]
 pub use interface_plugin::*;
+#[cfg(feature = "datatype-plugin")]
 pub use datatype_plugin::*;
+#[cfg(feature = "control-plugin")]
 pub use control_plugin::*;
+#[cfg(feature = "operator-plugin")]
 pub use operator_plugin::*;
+#[cfg(feature = "general-plugin")]
 pub use general_plugin::*;
+pub use keyword_table::KeywordTableRecognizer;
+pub use keyword_plugin::*;
+#[cfg(feature = "dynamic-plugins")]
+pub use dynamic_plugin::*;
+
+use crate::traits::LexerPlugin;
+
+/// Short names of the built-in plugins, as used in `LexerConfig::enabled_plugins`
+pub const BUILTIN_PLUGINS: &[&str] = &["module", "interface", "datatype", "control", "operator", "general"];
+
+/// Create a built-in plugin by short name. Returns `None` for unknown names and
+/// for plugins whose cargo feature is disabled.
+pub fn builtin_plugin(name: &str) -> Option<Box<dyn LexerPlugin>> {
+    match name {
+        #[cfg(feature = "module-plugin")]
+        "module" => Some(Box::new(ModulePlugin::default())),
+        #[cfg(feature = "interface-plugin")]
+        "interface" => Some(Box::new(InterfacePlugin::default())),
+        #[cfg(feature = "datatype-plugin")]
+        "datatype" => Some(Box::new(DataTypePlugin::default())),
+        #[cfg(feature = "control-plugin")]
+        "control" => Some(Box::new(ControlPlugin::default())),
+        #[cfg(feature = "operator-plugin")]
+        "operator" => Some(Box::new(OperatorPlugin::default())),
+        #[cfg(feature = "general-plugin")]
+        "general" => Some(Box::new(GeneralPlugin::default())),
+        _ => None,
+    }
+}
@@ -14,10 +14,12 @@ impl Default for ModuleTokenRecognizer {
                 Token::Module, Token::EndModule, Token::Package, 
                 Token::EndPackage, Token::Import, Token::Export,
                 Token::Generate, Token::EndGenerate, Token::GenVar,
+                Token::Program, Token::EndProgram,
             ],
             keywords: vec![
                 "module", "endmodule", "package", "endpackage",
-                "import", "export", "generate", "endgenerate", "genvar"
+                "import", "export", "generate", "endgenerate", "genvar",
+                "program", "endprogram"
             ],
         }
     }
@@ -41,16 +43,11 @@ impl TokenRecognizer for ModuleTokenRecognizer {
     }
 }
 
+#[derive(Default)]
 pub struct ModulePlugin {
     recognizer: Option<ModuleTokenRecognizer>,
 }
 
-impl Default for ModulePlugin {
-    fn default() -> Self {
-        Self { recognizer: None }
-    }
-}
-
 impl LexerPlugin for ModulePlugin {
     fn name(&self) -> &'static str {
         "SystemVerilog Module Plugin"
@@ -23,9 +23,12 @@ impl Default for OperatorTokenRecognizer {
                 Token::MultiplyAssign, Token::DivideAssign, Token::ModuloAssign,
                 Token::AndAssign, Token::OrAssign, Token::XorAssign,
                 Token::LeftShiftAssign, Token::RightShiftAssign,
-                Token::Increment, Token::Decrement,
+                Token::Increment, Token::Decrement, Token::ReductionNand,
+                Token::ReductionNor, Token::BitwiseXnor, Token::WildcardEqual,
+                Token::WildcardNotEqual, Token::Implication, Token::Equivalence,
+                Token::IndexedPlus, Token::IndexedMinus, Token::Inside,
             ],
-            keywords: vec![], // Operators don't have keyword forms
+            keywords: vec!["inside"],
         }
     }
 }
@@ -48,16 +51,11 @@ impl TokenRecognizer for OperatorTokenRecognizer {
     }
 }
 
+#[derive(Default)]
 pub struct OperatorPlugin {
     recognizer: Option<OperatorTokenRecognizer>,
 }
 
-impl Default for OperatorPlugin {
-    fn default() -> Self {
-        Self { recognizer: None }
-    }
-}
-
 impl LexerPlugin for OperatorPlugin {
     fn name(&self) -> &'static str {
         "SystemVerilog Operator Plugin"
@@ -0,0 +1,256 @@
+//! Tool pragmas: `// synopsys translate_off` style comments and `` `pragma ``
+//! directives.
+//!
+//! Comments never reach the token stream, so pragma comments are recovered from
+//! the trivia between tokens. [`PragmaFilter`] collects them from a token stream
+//! as it passes and removes `` `pragma `` lines before parsing and, in synthesis
+//! mode, every token inside a `translate_off` / `translate_on` region.
+
+use crate::lexer::SpannedToken;
+use crate::Token;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Comment prefixes that mark a tool pragma
+pub const PRAGMA_TOOLS: &[&str] = &["synopsys", "pragma", "synthesis", "cadence", "rtl_synthesis"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PragmaKind {
+    /// Start of a region hidden from synthesis
+    TranslateOff,
+    /// End of a region hidden from synthesis
+    TranslateOn,
+    /// Any other pragma comment, e.g. `// synopsys full_case parallel_case`
+    Comment,
+    /// A `` `pragma name args `` directive
+    Directive,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pragma {
+    pub kind: PragmaKind,
+    /// Comment prefix (`synopsys`, `pragma`, ...) or the directive's pragma name
+    pub name: String,
+    /// Remaining words of the comment, or the directive's argument text
+    pub args: String,
+    /// The whole comment or directive line
+    pub span: Range<usize>,
+}
+
+/// Pragmas of one file in source order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pragmas {
+    pub items: Vec<Pragma>,
+}
+
+impl Pragmas {
+    /// Collect pragmas from `source` and its complete token stream
+    pub fn scan(source: &str, tokens: &[SpannedToken]) -> Self {
+        let mut filter = PragmaFilter::new(source, tokens.iter().cloned(), false);
+        filter.by_ref().for_each(drop);
+        filter.into_pragmas()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Byte ranges from each `translate_off` to the end of its matching
+    /// `translate_on`, or to the end of the file if there is none
+    pub fn translate_off_regions(&self, source_len: usize) -> Vec<Range<usize>> {
+        let mut regions = Vec::new();
+        let mut open: Option<usize> = None;
+        for pragma in &self.items {
+            match pragma.kind {
+                PragmaKind::TranslateOff if open.is_none() => open = Some(pragma.span.start),
+                PragmaKind::TranslateOn => {
+                    if let Some(start) = open.take() {
+                        regions.push(start..pragma.span.end);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some(start) = open {
+            tracing::warn!("translate_off at byte {} is never turned back on", start);
+            regions.push(start..source_len);
+        }
+        regions
+    }
+}
+
+/// Token stream adapter between the lexer and the parser. Collects the pragmas
+/// of the trivia between tokens as they pass, drops `` `pragma `` directive
+/// lines and, with `synthesis`, everything in a translate_off region.
+pub struct PragmaFilter<'a, I> {
+    source: &'a str,
+    tokens: I,
+    synthesis: bool,
+    pragmas: Pragmas,
+    /// End of the previous token, where the trivia before the next one starts
+    position: usize,
+    /// Tokens starting before this offset are on a `` `pragma `` line
+    directive_end: usize,
+    /// Start of the open translate_off region
+    translate_off: Option<usize>,
+    finished: bool,
+}
+
+impl<'a, I: Iterator<Item = SpannedToken>> PragmaFilter<'a, I> {
+    pub fn new(source: &'a str, tokens: I, synthesis: bool) -> Self {
+        Self {
+            source,
+            tokens,
+            synthesis,
+            pragmas: Pragmas::default(),
+            position: 0,
+            directive_end: 0,
+            translate_off: None,
+            finished: false,
+        }
+    }
+
+    /// The pragmas seen so far; all of them once the stream is exhausted
+    pub fn into_pragmas(self) -> Pragmas {
+        self.pragmas
+    }
+
+    /// Collect the pragma comments in the trivia up to `end`
+    fn scan_trivia(&mut self, end: usize) {
+        let start = self.position;
+        for (offset, comment) in comments(&self.source[start..end]) {
+            let comment_start = start + offset;
+            let Some(pragma) = pragma_comment(comment, comment_start..comment_start + comment.len()) else {
+                continue;
+            };
+            match pragma.kind {
+                PragmaKind::TranslateOff if self.translate_off.is_none() => self.translate_off = Some(pragma.span.start),
+                PragmaKind::TranslateOn => self.translate_off = None,
+                _ => {}
+            }
+            self.pragmas.items.push(pragma);
+        }
+        self.position = end;
+    }
+}
+
+impl<I: Iterator<Item = SpannedToken>> Iterator for PragmaFilter<'_, I> {
+    type Item = SpannedToken;
+
+    fn next(&mut self) -> Option<SpannedToken> {
+        loop {
+            let Some((token, span)) = self.tokens.next() else {
+                if !self.finished {
+                    self.finished = true;
+                    self.scan_trivia(self.source.len());
+                    if let Some(start) = self.translate_off.filter(|_| self.synthesis) {
+                        tracing::warn!("translate_off at byte {} is never turned back on", start);
+                    }
+                }
+                return None;
+            };
+            self.scan_trivia(span.start);
+            self.position = span.end;
+            if token == Token::PreprocessorDirective && &self.source[span.clone()] == "`pragma" {
+                let pragma = pragma_directive(self.source, span.start);
+                self.directive_end = pragma.span.end;
+                self.pragmas.items.push(pragma);
+            }
+            let hidden = span.start < self.directive_end || (self.synthesis && self.translate_off.is_some());
+            if !hidden {
+                return Some((token, span));
+            }
+        }
+    }
+}
+
+/// Comments in a trivia string with their offsets
+fn comments(gap: &str) -> Vec<(usize, &str)> {
+    let mut found = Vec::new();
+    let mut position = 0;
+    while let Some(start) = gap[position..].find('/').map(|i| position + i) {
+        let rest = &gap[start..];
+        let len = if rest.starts_with("//") {
+            rest.find('\n').unwrap_or(rest.len())
+        } else if let Some(body) = rest.strip_prefix("/*") {
+            body.find("*/").map_or(rest.len(), |end| end + 4)
+        } else {
+            1
+        };
+        if len > 1 {
+            found.push((start, &rest[..len]));
+        }
+        position = start + len;
+    }
+    found
+}
+
+fn pragma_comment(comment: &str, span: Range<usize>) -> Option<Pragma> {
+    let body = comment.strip_prefix("//")
+        .or_else(|| comment.strip_prefix("/*").map(|body| body.strip_suffix("*/").unwrap_or(body)))?;
+    let mut words = body.split_whitespace();
+    let name = words.next().filter(|word| PRAGMA_TOOLS.contains(word))?;
+    let args: Vec<&str> = words.collect();
+
+    let kind = match (name, args.as_slice()) {
+        (_, ["translate_off", ..]) | ("rtl_synthesis", ["off", ..]) => PragmaKind::TranslateOff,
+        (_, ["translate_on", ..]) | ("rtl_synthesis", ["on", ..]) => PragmaKind::TranslateOn,
+        _ => PragmaKind::Comment,
+    };
+    Some(Pragma { kind, name: name.to_string(), args: args.join(" "), span })
+}
+
+/// A `` `pragma `` directive runs to the end of its line
+fn pragma_directive(source: &str, start: usize) -> Pragma {
+    let end = source[start..].find('\n').map_or(source.len(), |i| start + i);
+    let text = source[start + "`pragma".len()..end].trim();
+    let (name, args) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+    Pragma {
+        kind: PragmaKind::Directive,
+        name: name.to_string(),
+        args: args.trim().to_string(),
+        span: start..end,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_default_lexer;
+
+    #[test]
+    fn test_pragmas_and_synthesis_filter() {
+        let source = "module m;\n\
+            // synopsys translate_off\n\
+            initial $display(\"sim\");\n\
+            /* pragma translate_on */\n\
+            `pragma once extra args\n\
+            // synopsys full_case parallel_case\n\
+            // just a comment\n\
+            wire w;\n\
+            endmodule\n";
+        let tokens = create_default_lexer(source, "m.sv".to_string()).unwrap().tokenize().unwrap();
+        let pragmas = Pragmas::scan(source, &tokens);
+
+        let kinds: Vec<PragmaKind> = pragmas.items.iter().map(|pragma| pragma.kind).collect();
+        assert_eq!(kinds, [PragmaKind::TranslateOff, PragmaKind::TranslateOn, PragmaKind::Directive, PragmaKind::Comment]);
+        assert_eq!(&source[pragmas.items[0].span.clone()], "// synopsys translate_off");
+        assert_eq!((pragmas.items[2].name.as_str(), pragmas.items[2].args.as_str()), ("once", "extra args"));
+        assert_eq!(pragmas.items[3].args, "full_case parallel_case");
+
+        let filtered = |synthesis: bool| -> Vec<&str> {
+            let mut filter = PragmaFilter::new(source, tokens.iter().cloned(), synthesis);
+            let text = filter.by_ref().map(|(_, span)| &source[span]).collect();
+            assert_eq!(filter.into_pragmas(), pragmas);
+            text
+        };
+        assert_eq!(
+            filtered(false),
+            ["module", "m", ";", "initial", "$display", "(", "\"sim\"", ")", ";", "wire", "w", ";", "endmodule"]
+        );
+        assert_eq!(filtered(true), ["module", "m", ";", "wire", "w", ";", "endmodule"]);
+
+        let unterminated = Pragmas::scan("a // pragma translate_off\nb", &tokens[..0]);
+        assert_eq!(unterminated.translate_off_regions(27).first(), Some(&(2..27)));
+    }
+}
@@ -1,12 +1,39 @@
 use crate::traits::{LexerPlugin, TokenRecognizer};
 use crate::Token;
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 use tracing::{debug, info, warn};
 
 pub struct LexerRegistry {
     plugins: HashMap<String, Box<dyn LexerPlugin>>,
     recognizers: HashMap<String, Box<dyn TokenRecognizer>>,
     enabled_categories: HashSet<String>,
+    /// Categories in registration order, used to break priority ties
+    registration_order: Vec<String>,
+    /// Resolved owner category for every token claimed by a recognizer
+    token_owners: HashMap<Token, String>,
+    conflicts: Vec<TokenConflict>,
+    /// Identifier lexemes remapped to keyword tokens, with the priority that claimed them
+    keyword_tokens: HashMap<String, (Token, u8)>,
+}
+
+/// A token claimed by more than one recognizer
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenConflict {
+    pub token: Token,
+    /// Every category claiming the token with its priority, in registration order
+    pub claimants: Vec<(String, u8)>,
+    /// Category the token was resolved to
+    pub winner: String,
+}
+
+impl fmt::Display for TokenConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let claimants: Vec<String> = self.claimants.iter()
+            .map(|(category, priority)| format!("{} (priority {})", category, priority))
+            .collect();
+        write!(f, "{:?} claimed by {} -> resolved to '{}'", self.token, claimants.join(", "), self.winner)
+    }
 }
 
 impl LexerRegistry {
@@ -15,6 +42,10 @@ impl LexerRegistry {
             plugins: HashMap::new(),
             recognizers: HashMap::new(),
             enabled_categories: HashSet::new(),
+            registration_order: Vec::new(),
+            token_owners: HashMap::new(),
+            conflicts: Vec::new(),
+            keyword_tokens: HashMap::new(),
         }
     }
     
@@ -40,10 +71,27 @@ impl LexerRegistry {
         
         // Register recognizers if plugin is enabled
         if plugin.is_enabled() {
-            for recognizer in plugin.recognizers() {
+            let recognizers = plugin.recognizers();
+            
+            // Reject duplicate categories before touching any state
+            let mut seen = HashSet::new();
+            for recognizer in &recognizers {
+                let category = recognizer.category_name();
+                if self.recognizers.contains_key(category) || !seen.insert(category) {
+                    return Err(format!(
+                        "Plugin '{}' registers category '{}' which is already registered",
+                        name, category
+                    ));
+                }
+            }
+            
+            for recognizer in recognizers {
                 let category = recognizer.category_name().to_string();
                 debug!("Registering recognizer '{}' from plugin '{}'", category, name);
+                self.claim_tokens(&category, recognizer.as_ref());
+                self.claim_keywords(recognizer.as_ref());
                 self.recognizers.insert(category.clone(), recognizer);
+                self.registration_order.push(category.clone());
                 self.enabled_categories.insert(category);
             }
         }
@@ -53,9 +101,70 @@ impl LexerRegistry {
         Ok(())
     }
     
+    /// Record token ownership for a new recognizer, resolving conflicts by priority.
+    /// On equal priority the earlier registration keeps the token.
+    fn claim_tokens(&mut self, category: &str, recognizer: &dyn TokenRecognizer) {
+        let priority = recognizer.priority();
+        
+        for token in recognizer.tokens() {
+            let Some(owner) = self.token_owners.get(&token).cloned() else {
+                self.token_owners.insert(token, category.to_string());
+                continue;
+            };
+            if owner == category {
+                continue;
+            }
+            
+            let owner_priority = self.recognizers.get(&owner).map_or(0, |r| r.priority());
+            let winner = if priority > owner_priority { category } else { owner.as_str() }.to_string();
+            warn!(
+                "Token {:?} claimed by '{}' (priority {}) and '{}' (priority {}); using '{}'",
+                token, owner, owner_priority, category, priority, winner
+            );
+            
+            match self.conflicts.iter_mut().find(|c| c.token == token) {
+                Some(conflict) => {
+                    conflict.claimants.push((category.to_string(), priority));
+                    conflict.winner = winner.clone();
+                }
+                None => self.conflicts.push(TokenConflict {
+                    token: token.clone(),
+                    claimants: vec![(owner.clone(), owner_priority), (category.to_string(), priority)],
+                    winner: winner.clone(),
+                }),
+            }
+            self.token_owners.insert(token, winner);
+        }
+    }
+    
+    /// Record identifier lexemes a recognizer turns into keywords; higher priority wins
+    fn claim_keywords(&mut self, recognizer: &dyn TokenRecognizer) {
+        let priority = recognizer.priority();
+        for (keyword, token) in recognizer.keyword_tokens() {
+            match self.keyword_tokens.get(&keyword) {
+                Some((existing, existing_priority)) if *existing_priority >= priority => {
+                    if *existing != token {
+                        warn!(
+                            "Keyword '{}' already maps to {:?}; ignoring {:?} from '{}'",
+                            keyword, existing, token, recognizer.category_name()
+                        );
+                    }
+                }
+                _ => {
+                    self.keyword_tokens.insert(keyword, (token, priority));
+                }
+            }
+        }
+    }
+    
+    /// Keyword token for an identifier lexeme, if a recognizer claims it
+    pub fn keyword_token(&self, text: &str) -> Option<&Token> {
+        self.keyword_tokens.get(text).map(|(token, _)| token)
+    }
+    
     /// Get recognizer by category
-    pub fn get_recognizer(&self, category: &str) -> Option<&Box<dyn TokenRecognizer>> {
-        self.recognizers.get(category)
+    pub fn get_recognizer(&self, category: &str) -> Option<&dyn TokenRecognizer> {
+        self.recognizers.get(category).map(|r| r.as_ref())
     }
     
     /// List all enabled categories
@@ -69,6 +178,7 @@ impl LexerRegistry {
             total_plugins: self.plugins.len(),
             total_recognizers: self.recognizers.len(),
             enabled_categories: self.enabled_categories.len(),
+            conflicts: self.conflicts.len(),
         }
     }
     
@@ -77,11 +187,43 @@ impl LexerRegistry {
         self.recognizers.values().any(|recognizer| recognizer.handles_token(token))
     }
     
-    /// Get category name for a token
+    /// Get category name for a token.
+    ///
+    /// Tokens listed by a recognizer's `tokens()` resolve through the priority-ordered
+    /// ownership table; anything else falls back to `handles_token`, checked by descending
+    /// priority and then registration order.
     pub fn get_token_category(&self, token: &Token) -> Option<String> {
-        self.recognizers.iter()
-            .find(|(_, recognizer)| recognizer.handles_token(token))
-            .map(|(category, _)| category.clone())
+        if let Some(owner) = self.token_owners.get(token) {
+            return Some(owner.clone());
+        }
+        
+        let mut best: Option<(&String, u8)> = None;
+        for category in &self.registration_order {
+            let recognizer = &self.recognizers[category];
+            if recognizer.handles_token(token)
+                && best.is_none_or(|(_, priority)| recognizer.priority() > priority)
+            {
+                best = Some((category, recognizer.priority()));
+            }
+        }
+        best.map(|(category, _)| category.clone())
+    }
+    
+    /// Tokens claimed by more than one recognizer and how each was resolved
+    pub fn conflicts(&self) -> &[TokenConflict] {
+        &self.conflicts
+    }
+    
+    /// Human-readable report of all token conflicts
+    pub fn conflict_report(&self) -> String {
+        if self.conflicts.is_empty() {
+            return "No token conflicts".to_string();
+        }
+        let mut report = format!("{} token conflict(s):", self.conflicts.len());
+        for conflict in &self.conflicts {
+            report.push_str(&format!("\n  {}", conflict));
+        }
+        report
     }
     
     /// Enable/disable a category
@@ -100,6 +242,7 @@ pub struct RegistryStats {
     pub total_plugins: usize,
     pub total_recognizers: usize,
     pub enabled_categories: usize,
+    pub conflicts: usize,
 }
 
 impl Default for LexerRegistry {
@@ -107,3 +250,96 @@ impl Default for LexerRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestRecognizer {
+        category: &'static str,
+        priority: u8,
+        tokens: Vec<Token>,
+    }
+
+    impl TokenRecognizer for TestRecognizer {
+        fn tokens(&self) -> Vec<Token> { self.tokens.clone() }
+        fn keywords(&self) -> Vec<&'static str> { vec![] }
+        fn handles_token(&self, token: &Token) -> bool { self.tokens.contains(token) }
+        fn category_name(&self) -> &'static str { self.category }
+        fn priority(&self) -> u8 { self.priority }
+    }
+
+    struct TestPlugin {
+        name: &'static str,
+        category: &'static str,
+        priority: u8,
+        tokens: Vec<Token>,
+    }
+
+    impl LexerPlugin for TestPlugin {
+        fn name(&self) -> &'static str { self.name }
+        fn version(&self) -> &'static str { "0.0.1" }
+        fn description(&self) -> &'static str { "test plugin" }
+        fn recognizers(&self) -> Vec<Box<dyn TokenRecognizer>> {
+            vec![Box::new(TestRecognizer {
+                category: self.category,
+                priority: self.priority,
+                tokens: self.tokens.clone(),
+            })]
+        }
+        fn initialize(&mut self) -> Result<(), Box<dyn std::error::Error>> { Ok(()) }
+    }
+
+    #[test]
+    fn test_conflict_resolved_by_priority() {
+        let mut registry = LexerRegistry::new();
+        registry.register_plugin(Box::new(TestPlugin {
+            name: "general",
+            category: "general",
+            priority: 50,
+            tokens: vec![Token::Identifier, Token::Hash, Token::Comma],
+        })).unwrap();
+        registry.register_plugin(Box::new(TestPlugin {
+            name: "pragma",
+            category: "pragma",
+            priority: 80,
+            tokens: vec![Token::Identifier, Token::Hash],
+        })).unwrap();
+        registry.register_plugin(Box::new(TestPlugin {
+            name: "low",
+            category: "low",
+            priority: 10,
+            tokens: vec![Token::Hash],
+        })).unwrap();
+
+        assert_eq!(registry.get_token_category(&Token::Identifier).as_deref(), Some("pragma"));
+        assert_eq!(registry.get_token_category(&Token::Hash).as_deref(), Some("pragma"));
+        assert_eq!(registry.get_token_category(&Token::Comma).as_deref(), Some("general"));
+
+        let hash = registry.conflicts().iter().find(|c| c.token == Token::Hash).unwrap();
+        assert_eq!(hash.claimants.len(), 3);
+        assert_eq!(hash.winner, "pragma");
+        assert_eq!(registry.stats().conflicts, 2);
+    }
+
+    #[test]
+    fn test_duplicate_category_rejected() {
+        let mut registry = LexerRegistry::new();
+        registry.register_plugin(Box::new(TestPlugin {
+            name: "modules",
+            category: "module",
+            priority: 50,
+            tokens: vec![Token::Module],
+        })).unwrap();
+        let result = registry.register_plugin(Box::new(TestPlugin {
+            name: "other modules",
+            category: "module",
+            priority: 90,
+            tokens: vec![Token::Module],
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(registry.get_token_category(&Token::Module).as_deref(), Some("module"));
+        assert!(registry.conflicts().is_empty());
+    }
+}
@@ -21,6 +21,9 @@ pub trait TokenRecognizer: Send + Sync {
     
     /// Custom token validation logic
     fn validate_token(&self, _token: &Token, _text: &str) -> bool { true }
+    
+    /// Identifier lexemes this recognizer turns into keyword tokens
+    fn keyword_tokens(&self) -> Vec<(String, Token)> { vec![] }
 }
 
 /// Trait for lexer plugins - allows dynamic registration
@@ -48,21 +51,12 @@ pub trait LexerPlugin: Send + Sync {
 }
 
 /// Configuration for token recognition
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TokenConfig {
     pub ignore_case: bool,
-    pub custom_keywords: HashMap<String, Token>,
     pub disabled_tokens: Vec<Token>,
+    pub custom_keywords: HashMap<String, Token>,
 }
 
 pub struct TokenConfigSerdeWrapper(pub TokenConfig);
-
-impl Default for TokenConfig {
-    fn default() -> Self {
-        Self {
-            ignore_case: false,
-            custom_keywords: HashMap::new(),
-            disabled_tokens: Vec::new(),
-        }
-    }
-}
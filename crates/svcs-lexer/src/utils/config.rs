@@ -1,12 +1,25 @@
 use crate::traits::TokenConfig;
+use crate::plugins::KeywordPlugin;
+use crate::LexError;
 use serde::{Serialize, Deserialize};
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct LexerConfig {
-    pub token_config: TokenConfig,
     pub enabled_plugins: Vec<String>,
     pub disabled_plugins: Vec<String>,
     pub debug_mode: bool,
+    /// TOML keyword plugin definitions; relative paths resolve against `base_dir`
+    pub keyword_plugins: Vec<String>,
+    /// Directory of the config file, set by [`from_file`](Self::from_file).
+    /// Relative `keyword_plugins` paths are kept as written and only resolved
+    /// against it when loading, so saving the config does not rewrite them.
+    #[serde(skip)]
+    pub base_dir: Option<PathBuf>,
+    /// Last, because TOML requires tables to follow plain values; so do the
+    /// custom keywords within it
+    pub token_config: TokenConfig,
 }
 
 impl Default for LexerConfig {
@@ -23,6 +36,8 @@ impl Default for LexerConfig {
             ],
             disabled_plugins: vec![],
             debug_mode: false,
+            keyword_plugins: vec![],
+            base_dir: None,
         }
     }
 }
@@ -30,13 +45,76 @@ impl Default for LexerConfig {
 impl LexerConfig {
     pub fn from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let content = std::fs::read_to_string(path)?;
-        let config: LexerConfig = toml::from_str(&content)?;
+        let mut config: LexerConfig = toml::from_str(&content)?;
+        config.base_dir = Path::new(path).parent().map(Path::to_path_buf);
         Ok(config)
     }
     
+    /// Load the keyword plugins listed in `keyword_plugins`
+    pub fn load_keyword_plugins(&self) -> Result<Vec<KeywordPlugin>, LexError> {
+        self.keyword_plugins.iter()
+            .map(|path| match &self.base_dir {
+                Some(dir) => KeywordPlugin::from_file(&dir.join(path)),
+                None => KeywordPlugin::from_file(Path::new(path)),
+            })
+            .collect()
+    }
+    
     pub fn to_file(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
         let content = toml::to_string_pretty(self)?;
         std::fs::write(path, content)?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Token;
+
+    #[test]
+    fn test_every_field_survives_round_trip() {
+        let config = LexerConfig {
+            debug_mode: true,
+            token_config: TokenConfig {
+                disabled_tokens: vec![Token::Wire],
+                custom_keywords: [("acme_keep".to_string(), Token::CustomKeyword)].into(),
+                ..TokenConfig::default()
+            },
+            ..LexerConfig::default()
+        };
+        let path = std::env::temp_dir().join(format!("svcs-lexer-round-trip-{}.toml", std::process::id()));
+        config.to_file(&path.to_string_lossy()).unwrap();
+
+        let reloaded = LexerConfig::from_file(&path.to_string_lossy()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(reloaded.enabled_plugins, config.enabled_plugins);
+        assert!(reloaded.debug_mode);
+        assert_eq!(reloaded.token_config.disabled_tokens, [Token::Wire]);
+        assert_eq!(reloaded.token_config.custom_keywords.get("acme_keep"), Some(&Token::CustomKeyword));
+    }
+
+    #[test]
+    fn test_relative_plugin_paths_survive_round_trip() {
+        let dir = std::env::temp_dir().join(format!("svcs-lexer-config-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("plugins")).unwrap();
+        std::fs::write(
+            dir.join("plugins/acme.toml"),
+            "name = \"Acme\"\nversion = \"0.1.0\"\ncategory = \"acme\"\n[keywords]\nacme_keep = \"CustomKeyword\"\n",
+        ).unwrap();
+        let path = dir.join("lexer.toml");
+        let content = "keyword_plugins = [\"plugins/acme.toml\"]\n";
+        std::fs::write(&path, content).unwrap();
+
+        let config = LexerConfig::from_file(&path.to_string_lossy()).unwrap();
+        assert_eq!(config.keyword_plugins, ["plugins/acme.toml"]);
+        assert_eq!(config.load_keyword_plugins().unwrap().len(), 1);
+
+        let saved = dir.join("saved.toml");
+        config.to_file(&saved.to_string_lossy()).unwrap();
+        let reloaded = LexerConfig::from_file(&saved.to_string_lossy()).unwrap();
+        assert_eq!(reloaded.keyword_plugins, ["plugins/acme.toml"]);
+        assert_eq!(reloaded.load_keyword_plugins().unwrap().len(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
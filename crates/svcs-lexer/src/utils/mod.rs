@@ -1,5 +1,11 @@
 pub mod token_stats;
 pub mod config;
+pub mod line_index;
+pub mod token_dump;
+pub mod span_check;
 
 pub use token_stats::*;
 pub use config::*;
+pub use line_index::*;
+pub use token_dump::*;
+pub use span_check::*;
@@ -0,0 +1,129 @@
+//! Invariant checks for token streams, shared by the fuzz targets and property tests.
+
+use crate::lexer::SpannedToken;
+
+/// Check that spans are in order, do not overlap, lie within `input` and start
+/// and end on char boundaries
+pub fn check_spans(input: &str, tokens: &[SpannedToken]) -> Result<(), String> {
+    let mut previous_end = 0;
+    for (index, (token, span)) in tokens.iter().enumerate() {
+        if span.start >= span.end {
+            return Err(format!("token {} ({:?}) has empty span {:?}", index, token, span));
+        }
+        if span.start < previous_end {
+            return Err(format!(
+                "token {} ({:?}) at {:?} overlaps or precedes the previous token ending at {}",
+                index, token, span, previous_end
+            ));
+        }
+        if span.end > input.len() {
+            return Err(format!("token {} ({:?}) at {:?} is past the end of input", index, token, span));
+        }
+        if !input.is_char_boundary(span.start) || !input.is_char_boundary(span.end) {
+            return Err(format!("token {} ({:?}) at {:?} splits a character", index, token, span));
+        }
+        previous_end = span.end;
+    }
+    Ok(())
+}
+
+/// Text between consecutive tokens (and before the first and after the last),
+/// so that interleaving it with token texts reproduces `input`.
+/// Spans must already satisfy [`check_spans`].
+pub fn trivia<'a>(input: &'a str, tokens: &[SpannedToken]) -> Vec<&'a str> {
+    let mut gaps = Vec::with_capacity(tokens.len() + 1);
+    let mut position = 0;
+    for (_, span) in tokens {
+        gaps.push(&input[position..span.start]);
+        position = span.end;
+    }
+    gaps.push(&input[position..]);
+    gaps
+}
+
+/// Whether `text` consists only of whitespace and complete comments
+pub fn is_trivia(text: &str) -> bool {
+    let mut rest = text;
+    loop {
+        rest = rest.trim_start_matches([' ', '\t', '\r', '\n', '\x0c']);
+        if rest.is_empty() {
+            return true;
+        }
+        if let Some(comment) = rest.strip_prefix("//") {
+            rest = comment.find('\n').map_or("", |end| &comment[end..]);
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            match comment.find("*/") {
+                Some(end) => rest = &comment[end + 2..],
+                None => return false,
+            }
+        } else {
+            return false;
+        }
+    }
+}
+
+/// Rebuild the input from tokens and trivia, checking that every gap is trivia
+pub fn check_round_trip(input: &str, tokens: &[SpannedToken]) -> Result<(), String> {
+    check_spans(input, tokens)?;
+    let gaps = trivia(input, tokens);
+    let mut rebuilt = String::with_capacity(input.len());
+    for (index, gap) in gaps.iter().enumerate() {
+        if !is_trivia(gap) {
+            return Err(format!("text {:?} before token {} is not whitespace or comments", gap, index));
+        }
+        rebuilt.push_str(gap);
+        if let Some((_, span)) = tokens.get(index) {
+            rebuilt.push_str(&input[span.clone()]);
+        }
+    }
+    if rebuilt != input {
+        return Err("tokens and trivia do not reproduce the input".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_default_lexer;
+    use proptest::prelude::*;
+
+    const FRAGMENTS: &[&str] = &[
+        " ", "\n", "\t", "module", "endmodule", "assign", "a", "b1", "é", "名", "$display", "1",
+        "4'b10", "3.5", "(", ")", "[", "]", ";", ",", ".", ":", "::", "=", "==", "<=", "*", "/",
+        "// c\n", "/* c */", "/*", "*/", "\"s\"", "`define", "#", "@",
+    ];
+
+    fn text() -> impl Strategy<Value = String> {
+        prop::collection::vec(prop::sample::select(FRAGMENTS), 0..60).prop_map(|parts| parts.concat())
+    }
+
+    #[test]
+    fn test_check_spans_rejects_bad_streams() {
+        use crate::Token;
+        let input = "a é";
+        assert!(check_spans(input, &[(Token::Identifier, 0..1), (Token::Identifier, 2..4)]).is_ok());
+        assert!(check_spans(input, &[(Token::Identifier, 0..2), (Token::Identifier, 1..2)]).is_err());
+        assert!(check_spans(input, &[(Token::Identifier, 2..3)]).is_err());
+        assert!(check_spans(input, &[(Token::Identifier, 2..9)]).is_err());
+        assert!(check_round_trip("a x", &[(Token::Identifier, 0..1)]).is_err());
+        assert!(is_trivia(" // x\n/* y */\t"));
+        assert!(!is_trivia("/* open"));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_tokens_and_trivia_reproduce_input(input in text()) {
+            if let Ok(tokens) = create_default_lexer(&input, "fuzz.sv".to_string()).unwrap().tokenize() {
+                prop_assert_eq!(check_round_trip(&input, &tokens), Ok(()));
+            }
+        }
+
+        #[test]
+        fn prop_arbitrary_input_has_valid_spans(input in any::<String>()) {
+            let mut lexer = create_default_lexer(&input, "fuzz.sv".to_string()).unwrap();
+            let tokens: Vec<SpannedToken> = lexer.by_ref().map_while(Result::ok).collect();
+            prop_assert_eq!(check_spans(&input, &tokens), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{LexerRegistry, Token};
+use crate::utils::LineIndex;
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+use std::ops::Range;
+
+/// One token in a machine-readable dump
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenRecord {
+    pub kind: Token,
+    pub category: Option<String>,
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+}
+
+impl TokenRecord {
+    pub fn new(token: &Token, span: &Range<usize>, source: &str, lines: &LineIndex, registry: &LexerRegistry) -> Self {
+        let (line, column) = lines.line_col(source, span.start);
+        Self {
+            kind: token.clone(),
+            category: registry.get_token_category(token),
+            start: span.start,
+            end: span.end,
+            line,
+            column,
+            text: source[span.clone()].to_string(),
+        }
+    }
+}
+
+/// Output format of token dumps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TokenDumpFormat {
+    /// `Kind: 'text'`, one token per line
+    #[default]
+    Text,
+    /// One JSON [`TokenRecord`] object per line
+    JsonLines,
+}
+
+impl TokenDumpFormat {
+    /// File extension for dumps in this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            TokenDumpFormat::Text => "tokens",
+            TokenDumpFormat::JsonLines => "tokens.jsonl",
+        }
+    }
+}
+
+/// Streams tokens to a writer in a [`TokenDumpFormat`]
+pub struct TokenDumpWriter<'a, W: Write> {
+    writer: W,
+    format: TokenDumpFormat,
+    source: &'a str,
+    lines: LineIndex,
+    count: usize,
+}
+
+impl<'a, W: Write> TokenDumpWriter<'a, W> {
+    pub fn new(writer: W, format: TokenDumpFormat, source: &'a str) -> Self {
+        Self {
+            writer,
+            format,
+            source,
+            lines: LineIndex::new(source),
+            count: 0,
+        }
+    }
+
+    pub fn write_token(&mut self, token: &Token, span: &Range<usize>, registry: &LexerRegistry) -> io::Result<()> {
+        match self.format {
+            TokenDumpFormat::Text => {
+                if self.count > 0 {
+                    writeln!(self.writer)?;
+                }
+                write!(self.writer, "{:?}: '{}'", token, self.source[span.clone()].replace('\n', "\\n"))?;
+            }
+            TokenDumpFormat::JsonLines => {
+                let record = TokenRecord::new(token, span, self.source, &self.lines, registry);
+                serde_json::to_writer(&mut self.writer, &record)?;
+                writeln!(self.writer)?;
+            }
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(all(test, feature = "datatype-plugin"))]
+mod tests {
+    use super::*;
+    use crate::create_default_lexer;
+
+    #[test]
+    fn test_json_lines_dump() {
+        let source = "module m;\n/*é*/ wire w;\nendmodule";
+        let mut lexer = create_default_lexer(source, "test.sv".to_string()).unwrap();
+        let tokens = lexer.tokenize().unwrap();
+
+        let mut dump = TokenDumpWriter::new(Vec::new(), TokenDumpFormat::JsonLines, source);
+        for (token, span) in &tokens {
+            dump.write_token(token, span, lexer.registry()).unwrap();
+        }
+        let output = String::from_utf8(dump.finish().unwrap()).unwrap();
+        let records: Vec<TokenRecord> = output.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+
+        assert_eq!(records.len(), tokens.len());
+        assert_eq!(records[3].kind, Token::Wire);
+        assert_eq!(records[3].category.as_deref(), Some("datatype"));
+        assert_eq!((records[3].line, records[3].column), (2, 7));
+        assert_eq!((records[3].start, records[3].end), (17, 21));
+        assert_eq!(records[6].kind, Token::EndModule);
+        assert_eq!((records[6].line, records[6].column), (3, 1));
+    }
+}
@@ -1,50 +1,299 @@
 use crate::{Token, LexerRegistry};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
 use std::ops::Range;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct TokenStats {
     pub total_tokens: usize,
-    pub category_counts: HashMap<String, usize>,
-    pub token_type_counts: HashMap<String, usize>,
+    pub category_counts: BTreeMap<String, usize>,
+    pub token_type_counts: BTreeMap<String, usize>,
     pub file_path: String,
+    pub lines: LineCounts,
+}
+
+/// Source line classification. A line is code if any token touches it, blank if
+/// it is only whitespace, and comment otherwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineCounts {
+    pub total: usize,
+    pub code: usize,
+    pub comment: usize,
+    pub blank: usize,
+}
+
+impl LineCounts {
+    pub fn from_source(source: &str, tokens: &[(Token, Range<usize>)]) -> Self {
+        let mut counter = LineCounter::new(source);
+        for (_, span) in tokens {
+            counter.add(span);
+        }
+        counter.finish()
+    }
+
+    fn merge(&mut self, other: &LineCounts) {
+        self.total += other.total;
+        self.code += other.code;
+        self.comment += other.comment;
+        self.blank += other.blank;
+    }
+}
+
+/// Classifies the lines of a source file as its tokens pass by, so a token
+/// stream can be counted without collecting it. Tokens must come in order.
+pub struct LineCounter<'a> {
+    source: &'a str,
+    counts: LineCounts,
+    /// Start of the first line not classified yet
+    line_start: usize,
+    /// Start of the line after it, if there is one
+    next_line_start: Option<usize>,
+    /// Whether a token touches the line at `line_start`
+    is_code: bool,
+}
+
+impl<'a> LineCounter<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let mut counter = Self { source, counts: LineCounts::default(), line_start: 0, next_line_start: None, is_code: false };
+        counter.move_to(0);
+        counter
+    }
+
+    /// Mark every line the token at `span` overlaps as code
+    pub fn add(&mut self, span: &Range<usize>) {
+        while let Some(next) = self.next_line_start.filter(|&next| next <= span.start) {
+            self.classify_line(next);
+        }
+        self.is_code = true;
+        let end = span.end.max(span.start + 1);
+        while let Some(next) = self.next_line_start.filter(|&next| next < end) {
+            self.classify_line(next);
+            self.is_code = true;
+        }
+    }
+
+    pub fn finish(mut self) -> LineCounts {
+        while let Some(next) = self.next_line_start {
+            self.classify_line(next);
+        }
+        if self.line_start < self.source.len() {
+            self.classify_line(self.source.len());
+        }
+        self.counts
+    }
+
+    /// Count the line ending at `end` and move to the one after it
+    fn classify_line(&mut self, end: usize) {
+        self.counts.total += 1;
+        if self.is_code {
+            self.counts.code += 1;
+        } else if self.source[self.line_start..end].trim().is_empty() {
+            self.counts.blank += 1;
+        } else {
+            self.counts.comment += 1;
+        }
+        self.move_to(end);
+    }
+
+    fn move_to(&mut self, line_start: usize) {
+        self.line_start = line_start;
+        self.next_line_start = self.source[line_start..].find('\n').map(|i| line_start + i + 1);
+        self.is_code = false;
+    }
 }
 
 impl TokenStats {
     pub fn from_tokens(tokens: &[(Token, Range<usize>)], registry: &LexerRegistry) -> Self {
-        let mut category_counts = HashMap::new();
-        let mut token_type_counts = HashMap::new();
-        
+        let mut stats = Self { file_path: "unknown".to_string(), ..Default::default() };
         for (token, _) in tokens {
-            // Count by category
-            if let Some(category) = registry.get_token_category(token) {
-                *category_counts.entry(category).or_insert(0) += 1;
-            }
-            
-            // Count by token type
-            let token_name = format!("{:?}", token);
-            *token_type_counts.entry(token_name).or_insert(0) += 1;
+            stats.add_token(token, registry);
         }
-        
-        Self {
-            total_tokens: tokens.len(),
-            category_counts,
-            token_type_counts,
-            file_path: "unknown".to_string(),
+        stats
+    }
+
+    /// Count one more token, for streams that are not collected
+    pub fn add_token(&mut self, token: &Token, registry: &LexerRegistry) {
+        self.total_tokens += 1;
+
+        // Count by category
+        if let Some(category) = registry.get_token_category(token) {
+            *self.category_counts.entry(category).or_insert(0) += 1;
         }
+
+        // Count by token type
+        let token_name = format!("{:?}", token);
+        *self.token_type_counts.entry(token_name).or_insert(0) += 1;
+    }
+
+    /// Token counts plus line counts for a source file
+    pub fn from_source(
+        file_path: String,
+        source: &str,
+        tokens: &[(Token, Range<usize>)],
+        registry: &LexerRegistry,
+    ) -> Self {
+        let mut stats = Self::from_tokens(tokens, registry).with_file_path(file_path);
+        stats.lines = LineCounts::from_source(source, tokens);
+        stats
     }
-    
+
     pub fn with_file_path(mut self, file_path: String) -> Self {
         self.file_path = file_path;
         self
     }
-    
-    pub fn print_summary(&self) {
-        println!("Token Statistics for {}", self.file_path);
-        println!("Total tokens: {}", self.total_tokens);
-        println!("\nBy category:");
+
+    /// Share of all tokens in `category`, in percent
+    pub fn category_percentage(&self, category: &str) -> f64 {
+        let count = self.category_counts.get(category).copied().unwrap_or(0);
+        if self.total_tokens == 0 {
+            0.0
+        } else {
+            count as f64 * 100.0 / self.total_tokens as f64
+        }
+    }
+
+    /// Add another file's counts into this one
+    pub fn merge(&mut self, other: &TokenStats) {
+        self.total_tokens += other.total_tokens;
+        for (category, count) in &other.category_counts {
+            *self.category_counts.entry(category.clone()).or_insert(0) += count;
+        }
+        for (token, count) in &other.token_type_counts {
+            *self.token_type_counts.entry(token.clone()).or_insert(0) += count;
+        }
+        self.lines.merge(&other.lines);
+    }
+
+    pub fn summary(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "Token Statistics for {}", self.file_path);
+        let _ = writeln!(out, "Total tokens: {}", self.total_tokens);
+        let _ = writeln!(
+            out,
+            "Lines: {} ({} code, {} comment, {} blank)",
+            self.lines.total, self.lines.code, self.lines.comment, self.lines.blank
+        );
+        let _ = writeln!(out, "\nBy category:");
         for (category, count) in &self.category_counts {
-            println!("  {}: {}", category, count);
+            let _ = writeln!(out, "  {}: {} ({:.1}%)", category, count, self.category_percentage(category));
+        }
+        out
+    }
+
+    pub fn print_summary(&self) {
+        print!("{}", self.summary());
+    }
+}
+
+/// Per-file statistics with their aggregate
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatsReport {
+    pub files: Vec<TokenStats>,
+    pub total: TokenStats,
+}
+
+impl StatsReport {
+    pub fn new(files: Vec<TokenStats>) -> Self {
+        let mut total = TokenStats { file_path: "TOTAL".to_string(), ..Default::default() };
+        for stats in &files {
+            total.merge(stats);
+        }
+        Self { files, total }
+    }
+
+    pub fn summary(&self) -> String {
+        let mut out = String::new();
+        for stats in self.files.iter().chain(std::iter::once(&self.total)) {
+            out.push_str(&stats.summary());
+            out.push('\n');
         }
+        out
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// One row per file plus a `TOTAL` row, with a count and percentage column per category
+    pub fn to_csv(&self) -> String {
+        let categories: Vec<&String> = self.total.category_counts.keys().collect();
+
+        let mut out = String::from("file,total_tokens,total_lines,code_lines,comment_lines,blank_lines");
+        for category in &categories {
+            let _ = write!(out, ",{},{}_pct", csv_field(category), csv_field(category));
+        }
+        out.push('\n');
+
+        for stats in self.files.iter().chain(std::iter::once(&self.total)) {
+            let _ = write!(
+                out,
+                "{},{},{},{},{},{}",
+                csv_field(&stats.file_path),
+                stats.total_tokens,
+                stats.lines.total,
+                stats.lines.code,
+                stats.lines.comment,
+                stats.lines.blank
+            );
+            for category in &categories {
+                let count = stats.category_counts.get(category.as_str()).copied().unwrap_or(0);
+                let _ = write!(out, ",{},{:.2}", count, stats.category_percentage(category));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(all(test, feature = "module-plugin", feature = "datatype-plugin", feature = "general-plugin"))]
+mod tests {
+    use super::*;
+    use crate::create_default_lexer;
+
+    fn stats(path: &str, source: &str) -> TokenStats {
+        let mut lexer = create_default_lexer(source, path.to_string()).unwrap();
+        let (_, stats) = lexer.tokenize_with_stats().unwrap();
+        stats
+    }
+
+    #[test]
+    fn test_line_counts_and_aggregation() {
+        let a = stats("a.sv", "// header\n\nmodule a;\n  /* multi\n  line */\nendmodule\n");
+        assert_eq!(a.file_path, "a.sv");
+        assert_eq!(a.lines, LineCounts { total: 6, code: 2, comment: 3, blank: 1 });
+        assert_eq!(a.category_percentage("module"), 50.0);
+
+        let b = stats("b,1.sv", "module b; wire w; endmodule");
+        let report = StatsReport::new(vec![a, b]);
+        assert_eq!(report.total.total_tokens, 11);
+        assert_eq!(report.total.category_counts["module"], 4);
+        assert_eq!(report.total.lines.total, 7);
+
+        let csv = report.to_csv();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows[0], "file,total_tokens,total_lines,code_lines,comment_lines,blank_lines,datatype,datatype_pct,general,general_pct,module,module_pct");
+        assert_eq!(rows[2], "\"b,1.sv\",7,1,1,0,0,1,14.29,4,57.14,2,28.57");
+        assert!(rows[3].starts_with("TOTAL,11,7,3,3,1,"));
+
+        let json: StatsReport = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json, report);
+    }
+
+    #[test]
+    fn test_line_counts_of_spanning_tokens() {
+        // A string over two lines, then a last line without a newline
+        let source = "a \"x\ny\"\n\n// c\nb";
+        let tokens = [(Token::Identifier, 0..1), (Token::StringLiteral, 2..7), (Token::Identifier, 14..15)];
+        assert_eq!(LineCounts::from_source(source, &tokens), LineCounts { total: 5, code: 3, comment: 1, blank: 1 });
+        assert_eq!(LineCounts::from_source("", &[]), LineCounts::default());
     }
 }
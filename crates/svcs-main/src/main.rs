@@ -1,14 +1,22 @@
 use anyhow::{anyhow, Result};
+use rayon::prelude::*;
+use std::collections::HashMap;
 use std::fs;
+use std::io::BufWriter;
 use std::path::{Path, PathBuf};
-use svcs_cli::Args;
-use svcs_lexer::{create_default_lexer, Token};
-use svcs_lexer::utils::TokenStats;
-use svcs_parser::{parse_tokens_with_spans, Cst};
+use svcs_cli::{Args, StatsFormat, TokenFormat};
+use svcs_lexer::{Interner, LexerBuilder, LexerPlugin, PragmaFilter, SpannedToken, SystemVerilogLexer};
+use svcs_lexer::plugins::KeywordPlugin;
+#[cfg(feature = "dynamic-plugins")]
+use svcs_lexer::plugins::DynamicPlugin;
+use svcs_lexer::utils::{LexerConfig, LineCounter, StatsReport, TokenDumpFormat, TokenDumpWriter, TokenStats};
+use svcs_parser::ast::{self, AstNode};
+use svcs_parser::{parse_token_stream_with_interner, Cst, Parse};
 use svcs_preprocessor::preprocess;
 use svcs_analyzer::analyze;
 use svcs_logger::{log_stage, Logger};
-use tracing::{debug, error, info};
+use svcs_source::{FileId, SourceDb, SourceFile};
+use tracing::{debug, error, info, warn};
 
 fn main() -> Result<()> {
     // Parse command line arguments
@@ -36,93 +44,354 @@ fn main() -> Result<()> {
         }
     };
 
+    log_stage!("Loading Sources");
+    let mut sources = load_sources(&input_files)?;
+
     log_stage!("Preprocessing");
-    for file in &input_files {
-        debug!("Preprocessing file: {}", file.display());
-        process_file_preprocessing(file)?;
+    for source in sources.files() {
+        debug!("Preprocessing file: {}", source.path().display());
+        process_file_preprocessing(source)?;
     }
 
-    log_stage!("Lexical Analysis");
-    let mut all_tokens = Vec::new();
-    for file in &input_files {
-        debug!("Lexing file: {}", file.display());
-        let tokens = process_file_lexing(file)?;
-        all_tokens.push((file.clone(), tokens));
+    log_stage!("Lexical Analysis and Parsing");
+    let lexer_setup = LexerSetup::load(&args)?;
+    let interner = Interner::new();
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.jobs.unwrap_or(0))
+        .build()?;
+    info!("Lexing and parsing {} files on {} threads", sources.len(), pool.current_num_threads());
+    if args.synthesis {
+        info!("Synthesis mode: translate_off regions are dropped before parsing");
     }
 
-    log_stage!("Parsing");
+    // Results are collected in input order and every file writes to its own
+    // output name, so reporting and outputs are deterministic regardless of how
+    // files were scheduled
+    let output_names = output_names(&sources);
+    let results: Vec<Result<FileOutput>> = pool.install(|| {
+        sources.files().par_iter().zip(&output_names)
+            .map(|(source, name)| process_file_lexing_and_parsing(&sources, source, name, &lexer_setup, &interner))
+            .collect()
+    });
+
     let mut all_csts = Vec::new();
-    for (file, tokens) in all_tokens {
-        debug!("Parsing file: {}", file.display());
-        let cst = process_file_parsing(&file, tokens)?;
-        all_csts.push((file, cst));
+    let mut all_stats = Vec::new();
+    let mut all_includes = Vec::new();
+    let mut failures = 0;
+    for (source, result) in sources.files().iter().zip(results) {
+        match result {
+            Ok(output) => {
+                info!("Generated {} tokens from {}", output.stats.total_tokens, source.path().display());
+                info!("Tokens written to {}", output.tokens_path.display());
+                if output.pragmas > 0 {
+                    info!("Found {} pragmas in {}", output.pragmas, source.path().display());
+                }
+                info!("CST written to {}", output.cst_path.display());
+                all_stats.push(output.stats);
+                all_includes.push((source.id(), output.includes));
+                all_csts.push((source.id(), output.cst));
+            }
+            Err(e) => {
+                error!("{}", e);
+                failures += 1;
+            }
+        }
+    }
+    if failures > 0 {
+        return Err(anyhow!("{} of {} files failed lexing or parsing", failures, all_csts.len() + failures));
+    }
+    record_includes(&mut sources, all_includes);
+
+    if let Some(format) = args.stats {
+        write_stats_report(StatsReport::new(all_stats), format)?;
     }
 
     log_stage!("Semantic Analysis");
     for (file, cst) in all_csts {
-        debug!("Analyzing file: {}", file.display());
-        process_file_analysis(cst, &file)?;
+        let path = sources.path(file);
+        debug!("Analyzing file: {}", path.display());
+        process_file_analysis(cst, path)?;
     }
 
     info!("SVCS compilation completed successfully");
     Ok(())
 }
 
-fn process_file_preprocessing(file: &Path) -> Result<()> {
-    debug!("Preprocessing: {}", file.display());
-    let content = fs::read_to_string(file)?;
-    let _preprocessed = preprocess(&content)
-        .map_err(|e| anyhow!("Preprocessing failed for {}: {}", file.display(), e))?;
-    info!("Preprocessing completed for {}", file.display());
+/// Read every input file once; all later stages work from the database
+fn load_sources(files: &[PathBuf]) -> Result<SourceDb> {
+    let mut db = SourceDb::new();
+    for path in files {
+        db.load(path)?;
+    }
+    info!("Loaded {} source files", db.len());
+    Ok(db)
+}
+
+/// Name of each file's outputs under `out/`, in load order: the file stem, or
+/// `<stem>.<file id>` when several inputs share a stem (`rtl/top.sv`, `tb/top.sv`)
+fn output_names(sources: &SourceDb) -> Vec<String> {
+    let stem = |source: &SourceFile| {
+        source.path().file_stem().map_or_else(|| "unknown".to_string(), |s| s.to_string_lossy().into_owned())
+    };
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for source in sources.files() {
+        *counts.entry(stem(source)).or_default() += 1;
+    }
+    sources.files().iter()
+        .map(|source| match stem(source) {
+            name if counts[&name] > 1 => format!("{}.{}", name, source.id().as_u32()),
+            name => name,
+        })
+        .collect()
+}
+
+/// Load the files named by `` `include `` directives and record who includes
+/// them. Paths are resolved against the including file's directory.
+fn record_includes(sources: &mut SourceDb, includes: Vec<(FileId, Vec<String>)>) {
+    for (includer, paths) in includes {
+        let dir = sources.path(includer).parent().map(Path::to_path_buf).unwrap_or_default();
+        for path in paths {
+            match sources.load(&dir.join(&path)) {
+                Ok(included) => {
+                    debug!("{} includes {}", sources.path(includer).display(), sources.path(included).display());
+                    sources.add_include(includer, included);
+                }
+                Err(e) => warn!("Cannot resolve `include \"{}\" in {}: {}", path, sources.path(includer).display(), e),
+            }
+        }
+    }
+}
+
+fn process_file_preprocessing(source: &SourceFile) -> Result<()> {
+    debug!("Preprocessing: {}", source.path().display());
+    let _preprocessed = preprocess(source.text())
+        .map_err(|e| anyhow!("Preprocessing failed for {}: {}", source.path().display(), e))?;
+    info!("Preprocessing completed for {}", source.path().display());
     Ok(())
 }
 
-fn process_file_lexing(file: &Path) -> Result<Vec<(Token, std::ops::Range<usize>)>> {
-    debug!("Lexing: {}", file.display());
-    let content = fs::read_to_string(file)?;
-    let mut lexer = create_default_lexer(&content, file.display().to_string());
-    let (tokens, stats): (Vec<(Token, std::ops::Range<usize>)>, TokenStats) =
-        lexer.tokenize_with_stats()
-            .map_err(|e| anyhow!("Lexical analysis failed for {}: {}", file.display(), e))?;
+/// Lexer configuration and plugins, loaded once and shared by every input file
+struct LexerSetup {
+    config: LexerConfig,
+    token_format: TokenFormat,
+    synthesis: bool,
+    keyword_plugins: Vec<KeywordPlugin>,
+    #[cfg(feature = "dynamic-plugins")]
+    dynamic_plugins: Vec<DynamicPlugin>,
+}
+
+impl LexerSetup {
+    fn load(args: &Args) -> Result<Self> {
+        let config = match args.lexer_config {
+            Some(ref path) => {
+                info!("Loading lexer config from {}", path.display());
+                LexerConfig::from_file(&path.to_string_lossy())
+                    .map_err(|e| anyhow!("Failed to load lexer config {}: {}", path.display(), e))?
+            }
+            None => LexerConfig::default(),
+        };
+
+        let keyword_plugins = config.load_keyword_plugins()
+            .map_err(|e| anyhow!("Failed to load keyword plugin: {}", e))?
+            .into_iter()
+            .filter(|plugin| !config.disabled_plugins.iter().any(|name| name == plugin.name()))
+            .collect();
+
+        #[cfg(feature = "dynamic-plugins")]
+        let mut dynamic_plugins = Vec::new();
+        #[cfg(feature = "dynamic-plugins")]
+        for path in &args.lexer_plugins {
+            let plugin = DynamicPlugin::load(path)
+                .map_err(|e| anyhow!("Failed to load lexer plugin: {}", e))?;
+            dynamic_plugins.push(plugin);
+        }
+
+        Ok(Self {
+            config,
+            token_format: args.token_format,
+            synthesis: args.synthesis,
+            keyword_plugins,
+            #[cfg(feature = "dynamic-plugins")]
+            dynamic_plugins,
+        })
+    }
+
+    fn builder(&self) -> Result<LexerBuilder> {
+        let mut builder = LexerBuilder::new()
+            .with_lexer_config(&self.config)
+            .map_err(|e| anyhow!("Invalid lexer config: {}", e))?;
+        for plugin in &self.keyword_plugins {
+            builder = builder.with_plugin(Box::new(plugin.clone()));
+        }
+        #[cfg(feature = "dynamic-plugins")]
+        for plugin in &self.dynamic_plugins {
+            builder = builder.with_plugin(Box::new(plugin.clone()));
+        }
+        Ok(builder)
+    }
+}
+
+/// Everything produced for one file by the lexing and parsing stage
+struct FileOutput {
+    stats: TokenStats,
+    cst: Cst,
+    tokens_path: PathBuf,
+    cst_path: PathBuf,
+    pragmas: usize,
+    /// Paths named by `` `include `` directives, as written
+    includes: Vec<String>,
+}
+
+/// Lex and parse one file on a worker thread. Tokens stream from the lexer
+/// through the pragma filter into the parser, which buffers only its lookahead.
+fn process_file_lexing_and_parsing(
+    sources: &SourceDb,
+    source: &SourceFile,
+    output_name: &str,
+    lexer_setup: &LexerSetup,
+    interner: &Interner,
+) -> Result<FileOutput> {
+    let (mut lexed, tokens_path) = process_file_lexing(source, output_name, lexer_setup)?;
+
+    // Pragma directives never reach the parser; translate_off regions only in synthesis mode
+    let mut filtered = PragmaFilter::new(source.text(), lexed.by_ref(), lexer_setup.synthesis);
+    debug!("Parsing: {}", source.path().display());
+    let parse = parse_token_stream_with_interner(source, filtered.by_ref(), interner);
+    let pragmas = filtered.into_pragmas();
+    // A lexical error ends the stream early, so the tree is not worth writing
+    let stats = lexed.finish()?;
+    for pragma in &pragmas.items {
+        debug!("{}: {:?} pragma {} {} at {:?}", source.path().display(), pragma.kind, pragma.name, pragma.args, pragma.span);
+    }
+
+    let (cst, cst_path) = process_file_parsing(sources, source, output_name, parse)?;
+    let includes = ast::SourceFile::cast(cst.syntax())
+        .map(|root| root.directives().filter_map(|directive| directive.include_path()).collect())
+        .unwrap_or_default();
+    Ok(FileOutput { stats, cst, tokens_path, cst_path, pragmas: pragmas.items.len(), includes })
+}
 
-    info!("Generated {} tokens from {}", stats.total_tokens, file.display());
+/// Set up the lexer of one file and the token dump it streams to
+fn process_file_lexing<'s>(
+    source: &'s SourceFile,
+    output_name: &str,
+    lexer_setup: &LexerSetup,
+) -> Result<(LexedTokens<'s>, PathBuf)> {
+    let file = source.path();
+    debug!("Lexing: {}", file.display());
+    let lexer = lexer_setup.builder()?
+        .build(source.text(), file.display().to_string())
+        .map_err(|e| anyhow!("Failed to set up lexer for {}: {}", file.display(), e))?;
 
-    // Write tokens to out/lexer/tokens/<file>.tokens
+    // Stream tokens to out/lexer/tokens/<output name>.<ext> as they are lexed
+    let format = match lexer_setup.token_format {
+        TokenFormat::Text => TokenDumpFormat::Text,
+        TokenFormat::Jsonl => TokenDumpFormat::JsonLines,
+    };
     let mut out_dir = PathBuf::from("out/lexer/tokens");
     fs::create_dir_all(&out_dir)?;
-    let file_stem = file.file_stem().and_then(|s| s.to_str()).unwrap_or("unknown");
-    out_dir.push(format!("{}.tokens", file_stem));
+    out_dir.push(format!("{}.{}", output_name, format.extension()));
+    let dump = TokenDumpWriter::new(BufWriter::new(fs::File::create(&out_dir)?), format, source.text());
 
-    let mut lines = Vec::with_capacity(tokens.len());
-    for (token, span) in &tokens {
-        let text = &content[span.clone()];
-        lines.push(format!("{:?}: '{}'", token, text.replace('\n', "\\n")));
+    let lexed = LexedTokens {
+        source,
+        lexer,
+        dump,
+        stats: TokenStats::default().with_file_path(file.display().to_string()),
+        lines: LineCounter::new(source.text()),
+        error: None,
+    };
+    Ok((lexed, out_dir))
+}
+
+/// Tokens of one file as the lexer produces them. Each token is dumped and
+/// counted on its way through, so none are kept. An error ends the stream and
+/// is returned by [`finish`](LexedTokens::finish).
+struct LexedTokens<'s> {
+    source: &'s SourceFile,
+    lexer: SystemVerilogLexer<'s>,
+    dump: TokenDumpWriter<'s, BufWriter<fs::File>>,
+    stats: TokenStats,
+    lines: LineCounter<'s>,
+    error: Option<anyhow::Error>,
+}
+
+impl LexedTokens<'_> {
+    /// Flush the token dump and return the file's statistics, or the error that
+    /// ended the stream
+    fn finish(self) -> Result<TokenStats> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        self.dump.finish()?;
+        let mut stats = self.stats;
+        stats.lines = self.lines.finish();
+        debug!("Lexed {} tokens from {}", stats.total_tokens, self.source.path().display());
+        Ok(stats)
     }
-    fs::write(&out_dir, lines.join("\n"))?;
-    info!("Tokens written to {}", out_dir.display());
+}
+
+impl Iterator for LexedTokens<'_> {
+    type Item = SpannedToken;
 
-    Ok(tokens)
+    fn next(&mut self) -> Option<SpannedToken> {
+        if self.error.is_some() {
+            return None;
+        }
+        let (token, span) = match self.lexer.next()? {
+            Ok(token) => token,
+            Err(e) => {
+                self.error = Some(anyhow!("Lexical analysis failed for {}: {}", self.source.path().display(), e));
+                return None;
+            }
+        };
+        if let Err(e) = self.dump.write_token(&token, &span, self.lexer.registry()) {
+            self.error = Some(e.into());
+            return None;
+        }
+        self.stats.add_token(&token, self.lexer.registry());
+        self.lines.add(&span);
+        Some((token, span))
+    }
+}
+
+fn write_stats_report(report: StatsReport, format: StatsFormat) -> Result<()> {
+    let (extension, content) = match format {
+        StatsFormat::Text => ("txt", report.summary()),
+        StatsFormat::Json => ("json", report.to_json()?),
+        StatsFormat::Csv => ("csv", report.to_csv()),
+    };
+    let out_dir = Path::new("out/lexer");
+    fs::create_dir_all(out_dir)?;
+    let path = out_dir.join(format!("stats.{}", extension));
+    fs::write(&path, content)?;
+    info!(
+        "Token statistics for {} files written to {}",
+        report.files.len(),
+        path.display()
+    );
+    Ok(())
 }
 
 fn process_file_parsing(
-    file: &Path,
-    tokens: Vec<(Token, std::ops::Range<usize>)>,
-) -> Result<Cst> {
-    debug!("Parsing: {}", file.display());
-    let content = fs::read_to_string(file)?;
-    let cst = parse_tokens_with_spans(&content, &tokens)
-        .map_err(|e| anyhow!("Parsing failed for {}: {}", file.display(), e))?;
-    info!("Parsed CST for {}", file.display());
-    // === Write CST to disk ===
+    sources: &SourceDb,
+    source: &SourceFile,
+    output_name: &str,
+    parse: Parse,
+) -> Result<(Cst, PathBuf)> {
+    let file = source.path();
+    debug!("Parsed CST for {}", file.display());
+    // === Write CST to disk, with Error nodes if the file has syntax errors ===
     let cst_out_dir = Path::new("out/parser/cst");
     fs::create_dir_all(cst_out_dir)?;
-    let cst_file_path = cst_out_dir.join(format!(
-        "{}.cst",
-        file.file_stem().unwrap().to_string_lossy()
-    ));
-    fs::write(&cst_file_path, cst.to_string())?;
-    info!("CST written to {}", cst_file_path.display());
-    Ok(cst)
+    let cst_file_path = cst_out_dir.join(format!("{}.cst", output_name));
+    fs::write(&cst_file_path, parse.cst.to_string())?;
+    let cst = parse.ok().map_err(|errors| {
+        let count = errors.0.len();
+        let located: Vec<String> = errors.0.iter().map(|e| format!("{}:{}", sources.path(e.span.file).display(), e)).collect();
+        anyhow!("Parsing failed for {} with {} syntax error(s):\n{}", file.display(), count, located.join("\n"))
+    })?;
+    Ok((cst, cst_file_path))
 }
 
 fn process_file_analysis(cst: Cst, file: &Path) -> Result<()> {
@@ -0,0 +1,74 @@
+//! Lexer plugins loaded from a shared library, built from crates/svcs-test-plugin.
+#![cfg(feature = "dynamic-plugins")]
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+use svcs_lexer::plugins::DynamicPlugin;
+use svcs_lexer::{LexError, LexerBuilder, Token};
+
+/// Build the test plugin once and return the path of its shared library
+fn plugin_library() -> &'static PathBuf {
+    static LIBRARY: OnceLock<PathBuf> = OnceLock::new();
+    LIBRARY.get_or_init(|| {
+        let output = Command::new(env!("CARGO"))
+            .args(["build", "--quiet", "--package", "svcs-test-plugin", "--message-format=json"])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .lines()
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .filter(|message| message["reason"] == "compiler-artifact" && message["target"]["name"] == "svcs_test_plugin")
+            .flat_map(|message| message["filenames"].as_array().cloned().unwrap_or_default())
+            .filter_map(|file| file.as_str().map(PathBuf::from))
+            .find(|path| path.extension().is_some_and(|extension| extension == std::env::consts::DLL_EXTENSION))
+            .expect("cargo reported no shared library for svcs-test-plugin")
+    })
+}
+
+#[test]
+fn test_load_plugin_library() {
+    let plugin = DynamicPlugin::load(plugin_library()).unwrap();
+    let mut lexer = LexerBuilder::new()
+        .with_default_plugins()
+        .with_plugin(Box::new(plugin))
+        .build("test_keep wire w;", "test.sv".to_string())
+        .unwrap();
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(tokens[0].0, Token::CustomKeyword);
+    assert_eq!(tokens[1].0, Token::Wire);
+    assert_eq!(lexer.registry().get_token_category(&Token::CustomKeyword).as_deref(), Some("test"));
+}
+
+#[test]
+fn test_load_rejects_non_library() {
+    let path = std::env::temp_dir().join(format!("svcs-not-a-plugin-{}.so", std::process::id()));
+    fs::write(&path, "not a shared library").unwrap();
+    let result = DynamicPlugin::load(&path);
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(result, Err(LexError::PluginError { ref message }) if message.contains("svcs-not-a-plugin")));
+}
+
+#[test]
+fn test_lexer_plugin_option() {
+    let dir = std::env::temp_dir().join(format!("svcs-plugin-test-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    // The parser keeps the rest of a directive line as is, so the keyword parses
+    fs::write(dir.join("top.sv"), "`pragma test_keep\nmodule top; endmodule\n").unwrap();
+
+    let status = Command::new(env!("CARGO_BIN_EXE_svcs"))
+        .current_dir(&dir)
+        .arg("--lexer-plugin")
+        .arg(plugin_library())
+        .args(["--token-format", "jsonl", "-i", "top.sv"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let tokens = fs::read_to_string(dir.join("out/lexer/tokens/top.tokens.jsonl")).unwrap();
+    assert!(tokens.lines().any(|line| line.contains("\"kind\":\"CustomKeyword\"") && line.contains("\"category\":\"test\"")));
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,67 @@
+//! The outputs of a run do not depend on the number of worker threads.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Every file under `dir`, by path relative to `dir`
+fn read_tree(dir: &Path) -> BTreeMap<PathBuf, Vec<u8>> {
+    let mut files = BTreeMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in fs::read_dir(&current).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                pending.push(path);
+            } else {
+                files.insert(path.strip_prefix(dir).unwrap().to_path_buf(), fs::read(&path).unwrap());
+            }
+        }
+    }
+    files
+}
+
+/// Run svcs in `work` and return its lexer and parser outputs; logs carry
+/// timestamps and are left out
+fn run(work: &Path, jobs: usize, inputs: &[PathBuf]) -> BTreeMap<PathBuf, Vec<u8>> {
+    fs::create_dir_all(work).unwrap();
+    let status = Command::new(env!("CARGO_BIN_EXE_svcs"))
+        .current_dir(work)
+        .arg(format!("-j{}", jobs))
+        .args(["--stats", "csv", "--token-format", "jsonl", "-i"])
+        .args(inputs)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let mut outputs = read_tree(&work.join("out/lexer"));
+    outputs.extend(read_tree(&work.join("out/parser")).into_iter().map(|(path, text)| (Path::new("parser").join(path), text)));
+    outputs
+}
+
+#[test]
+fn test_outputs_identical_for_any_job_count() {
+    let dir = std::env::temp_dir().join(format!("svcs-jobs-test-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let mut inputs = Vec::new();
+    for (sub, body) in [("rtl", "wire a;"), ("tb", "logic [3:0] b; initial b = 1;"), ("ip", "assign y = x;")] {
+        fs::create_dir_all(dir.join(sub)).unwrap();
+        for name in ["top", sub] {
+            let path = dir.join(sub).join(format!("{}.sv", name));
+            fs::write(&path, format!("module {}_{}; {} endmodule\n", sub, name, body)).unwrap();
+            inputs.push(path);
+        }
+    }
+
+    let serial = run(&dir.join("j1"), 1, &inputs);
+    let parallel = run(&dir.join("j4"), 4, &inputs);
+    assert_eq!(serial.keys().collect::<Vec<_>>(), parallel.keys().collect::<Vec<_>>());
+    assert!(serial == parallel);
+
+    // Inputs sharing a stem get one output each
+    for name in ["top.0", "top.2", "top.4", "rtl", "tb", "ip"] {
+        assert!(serial.contains_key(&Path::new("parser/cst").join(format!("{}.cst", name))), "{}", name);
+        assert!(serial.contains_key(&Path::new("tokens").join(format!("{}.tokens.jsonl", name))), "{}", name);
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
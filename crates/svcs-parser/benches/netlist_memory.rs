@@ -0,0 +1,119 @@
+//! Peak heap usage on a synthetic gate-level netlist, 256 MiB by default: the
+//! lexer's `tokenize` versus its streaming iterator, then parsing with the
+//! pragma filter from a collected token `Vec` versus straight from the lexer,
+//! which is how the `svcs` binary reads each file.
+//!
+//! ```sh
+//! SVCS_BENCH_MB=512 cargo bench -p svcs-parser --bench netlist_memory
+//! ```
+//!
+//! The parse rows include the tree itself, so the difference between them is
+//! the token `Vec` the streaming path never builds.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+use svcs_lexer::{create_default_lexer, PragmaFilter};
+use svcs_parser::{parse_token_stream, parse_tokens};
+use svcs_source::SourceDb;
+
+struct CountingAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Generate a flat netlist of standard-cell instances of roughly `bytes` bytes
+fn netlist(bytes: usize) -> String {
+    let mut out = String::with_capacity(bytes + 256);
+    out.push_str("module top(input clk, input rst_n, output y);\n");
+    let mut i = 0usize;
+    while out.len() < bytes {
+        out.push_str(&format!(
+            "  NAND2X1 u_nand_{i} (.A(n_{i}), .B(n_{j}), .Y(n_{k}));\n  DFFRX1 u_ff_{i} (.D(n_{k}), .CK(clk), .RN(rst_n), .Q(q_{i}));\n",
+            i = i,
+            j = i + 1,
+            k = i + 2
+        ));
+        i += 1;
+    }
+    out.push_str("endmodule\n");
+    out
+}
+
+/// Run `f` and return its result with the peak heap growth above the starting point
+fn measure<T>(f: impl FnOnce() -> T) -> (T, usize, f64) {
+    let base = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed().as_secs_f64();
+    (result, PEAK.load(Ordering::Relaxed) - base, elapsed)
+}
+
+fn mib(bytes: usize) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+fn main() {
+    let megabytes: usize = std::env::var("SVCS_BENCH_MB")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(256);
+    let input = netlist(megabytes * 1024 * 1024);
+    println!("netlist: {:.1} MiB", mib(input.len()));
+
+    let (count, peak, secs) = measure(|| {
+        let mut lexer = create_default_lexer(&input, "netlist.v".to_string()).unwrap();
+        lexer.tokenize().unwrap().len()
+    });
+    println!("tokenize:  {:>10} tokens  peak heap {:>9.1} MiB  {:.2}s", count, mib(peak), secs);
+
+    let (count, peak, secs) = measure(|| {
+        let lexer = create_default_lexer(&input, "netlist.v".to_string()).unwrap();
+        let mut count = 0;
+        for token in lexer {
+            token.unwrap();
+            count += 1;
+        }
+        count
+    });
+    println!("streaming: {:>10} tokens  peak heap {:>9.1} MiB  {:.2}s", count, mib(peak), secs);
+
+    let mut db = SourceDb::new();
+    let file = db.add_file("netlist.v", input);
+    let source = db.file(file);
+
+    let (errors, peak, secs) = measure(|| {
+        let mut lexer = create_default_lexer(source.text(), "netlist.v".to_string()).unwrap();
+        let tokens = lexer.tokenize().unwrap();
+        let tokens: Vec<_> = PragmaFilter::new(source.text(), tokens.into_iter(), false).collect();
+        parse_tokens(source, &tokens).errors.len()
+    });
+    println!("parse collected: {:>4} errors  peak heap {:>9.1} MiB  {:.2}s", errors, mib(peak), secs);
+
+    let (errors, peak, secs) = measure(|| {
+        let lexer = create_default_lexer(source.text(), "netlist.v".to_string()).unwrap();
+        let filtered = PragmaFilter::new(source.text(), lexer.map(Result::unwrap), false);
+        parse_token_stream(source, filtered).errors.len()
+    });
+    println!("parse streamed:  {:>4} errors  peak heap {:>9.1} MiB  {:.2}s", errors, mib(peak), secs);
+}
@@ -0,0 +1,1222 @@
+//! Typed views over the CST.
+//!
+//! Each type wraps a [`SyntaxNode`] of one [`SyntaxKind`] and exposes its parts by
+//! name, so consumers do not walk children or compare kinds themselves.
+//! Accessors return `None` (or nothing) for parts missing from erroneous input.
+
+use crate::{SyntaxKind, SyntaxNode, SyntaxToken};
+
+/// A typed node backed by a syntax node of a fixed kind
+pub trait AstNode: Sized {
+    fn can_cast(kind: SyntaxKind) -> bool;
+
+    fn cast(node: SyntaxNode) -> Option<Self>;
+
+    fn syntax(&self) -> &SyntaxNode;
+}
+
+/// Nodes that declare a name: the first identifier among their direct tokens
+pub trait HasName: AstNode {
+    fn name(&self) -> Option<String> {
+        self.name_token().map(|token| token.text().to_string())
+    }
+
+    fn name_token(&self) -> Option<SyntaxToken> {
+        token(self.syntax(), SyntaxKind::Identifier)
+    }
+}
+
+macro_rules! ast_nodes {
+    ($($(#[$meta:meta])* $name:ident),* $(,)?) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+            pub struct $name(SyntaxNode);
+
+            impl AstNode for $name {
+                fn can_cast(kind: SyntaxKind) -> bool {
+                    kind == SyntaxKind::$name
+                }
+
+                fn cast(node: SyntaxNode) -> Option<Self> {
+                    Self::can_cast(node.kind()).then_some(Self(node))
+                }
+
+                fn syntax(&self) -> &SyntaxNode {
+                    &self.0
+                }
+            }
+        )*
+    };
+}
+
+ast_nodes! {
+    SourceFile,
+    ModuleDeclaration,
+    InterfaceDeclaration,
+    ProgramDeclaration,
+    PackageDeclaration,
+    PortList,
+    /// An ANSI port in the header, or a bare non-ANSI port name
+    Port,
+    /// A port declaration among the module items of a non-ANSI module
+    PortDeclaration,
+    /// `bus_if.master` or `interface` in front of an interface port name
+    InterfacePortHeader,
+    DataType,
+    /// `struct` or `union` with its members
+    StructUnionType,
+    StructMember,
+    EnumType,
+    EnumMember,
+    PackedDimension,
+    /// Any variable dimension: fixed, dynamic, associative or queue
+    UnpackedDimension,
+    NetDeclaration,
+    /// `(strong0, weak1)` after a net type
+    DriveStrength,
+    /// `#5` or `#(1, 2)` in a net declaration
+    Delay3,
+    DataDeclaration,
+    TypedefDeclaration,
+    ParameterDeclaration,
+    /// `#( ... )` after the name of a module, interface or program
+    ParameterPortList,
+    /// One comma-separated entry of a parameter port list
+    ParameterPortDeclaration,
+    /// `T = logic` in a type parameter declaration
+    TypeAssignment,
+    Declarator,
+    FunctionDeclaration,
+    TaskDeclaration,
+    ContinuousAssign,
+    NetAssignment,
+    AlwaysConstruct,
+    /// `@(...)`, `@*` or `@name`
+    EventControl,
+    EventExpression,
+    SeqBlock,
+    /// `fork ... join`, `join_any` or `join_none`
+    ParBlock,
+    IfStatement,
+    CaseStatement,
+    CaseItem,
+    ModuleInstantiation,
+    HierarchicalInstance,
+    NamedPortConnection,
+    /// `.name`, connecting the port to the signal of the same name
+    ImplicitPortConnection,
+    /// `.*`, connecting all remaining ports by name
+    WildcardPortConnection,
+    OrderedPortConnection,
+    /// `#( ... )` between the module name and the instances
+    ParameterValueAssignment,
+    NamedParameterAssignment,
+    OrderedParameterAssignment,
+    /// A compiler directive such as `` `include "defs.svh" `` with the rest of its line
+    Directive,
+}
+
+impl HasName for ModuleDeclaration {}
+impl HasName for InterfaceDeclaration {}
+impl HasName for ProgramDeclaration {}
+impl HasName for PackageDeclaration {}
+impl HasName for Port {}
+impl HasName for Declarator {}
+impl HasName for TypeAssignment {}
+impl HasName for EnumMember {}
+impl HasName for TypedefDeclaration {}
+impl HasName for FunctionDeclaration {}
+impl HasName for TaskDeclaration {}
+impl HasName for HierarchicalInstance {}
+
+/// First direct child token of `kind`
+fn token(node: &SyntaxNode, kind: SyntaxKind) -> Option<SyntaxToken> {
+    node.children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .find(|token| token.kind() == kind)
+}
+
+/// First direct child token that is not trivia
+fn first_token(node: &SyntaxNode) -> Option<SyntaxToken> {
+    node.children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .find(|token| !token.kind().is_trivia())
+}
+
+fn child<N: AstNode>(node: &SyntaxNode) -> Option<N> {
+    node.children().find_map(N::cast)
+}
+
+fn children<N: AstNode>(node: &SyntaxNode) -> impl Iterator<Item = N> {
+    node.children().filter_map(N::cast)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortDirection {
+    Input,
+    Output,
+    Inout,
+    Ref,
+}
+
+impl PortDirection {
+    fn from_kind(kind: SyntaxKind) -> Option<Self> {
+        match kind {
+            SyntaxKind::Input => Some(Self::Input),
+            SyntaxKind::Output => Some(Self::Output),
+            SyntaxKind::Inout => Some(Self::Inout),
+            SyntaxKind::Ref => Some(Self::Ref),
+            _ => None,
+        }
+    }
+}
+
+fn direction(node: &SyntaxNode) -> Option<PortDirection> {
+    first_token(node).and_then(|token| PortDirection::from_kind(token.kind()))
+}
+
+/// Whether a port is a net or a variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortKind {
+    /// A net of the given net type keyword, e.g. `Wire` or `Tri0`
+    Net(SyntaxKind),
+    Var,
+}
+
+impl PortKind {
+    fn from_kind(kind: SyntaxKind) -> Option<Self> {
+        match kind {
+            SyntaxKind::Wire
+            | SyntaxKind::Tri
+            | SyntaxKind::TriAnd
+            | SyntaxKind::TriOr
+            | SyntaxKind::Tri0
+            | SyntaxKind::Tri1
+            | SyntaxKind::TriReg
+            | SyntaxKind::WAnd
+            | SyntaxKind::WOr
+            | SyntaxKind::Supply0
+            | SyntaxKind::Supply1
+            | SyntaxKind::UWire => Some(Self::Net(kind)),
+            SyntaxKind::Var => Some(Self::Var),
+            _ => None,
+        }
+    }
+}
+
+/// Explicit net type or `var` keyword among the direct tokens
+fn port_kind(node: &SyntaxNode) -> Option<PortKind> {
+    node.children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .find_map(|token| PortKind::from_kind(token.kind()))
+}
+
+/// A header port with the direction, kind and data type it declares or inherits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortSignature {
+    pub port: Port,
+    pub direction: Option<PortDirection>,
+    pub kind: Option<PortKind>,
+    pub data_type: Option<DataType>,
+    pub interface: Option<InterfacePortHeader>,
+}
+
+impl SourceFile {
+    pub fn modules(&self) -> impl Iterator<Item = ModuleDeclaration> {
+        children(&self.0)
+    }
+
+    pub fn interfaces(&self) -> impl Iterator<Item = InterfaceDeclaration> {
+        children(&self.0)
+    }
+
+    pub fn packages(&self) -> impl Iterator<Item = PackageDeclaration> {
+        children(&self.0)
+    }
+
+    /// Compiler directives at any depth, in source order
+    pub fn directives(&self) -> impl Iterator<Item = Directive> {
+        self.0.descendants().filter_map(Directive::cast)
+    }
+}
+
+impl Directive {
+    /// The directive with its backtick, e.g. `` `include ``
+    pub fn name(&self) -> Option<String> {
+        token(&self.0, SyntaxKind::PreprocessorDirective).map(|token| token.text().to_string())
+    }
+
+    /// File named by an `` `include ``, from between the quotes or angle brackets
+    pub fn include_path(&self) -> Option<String> {
+        if self.name().as_deref() != Some("`include") {
+            return None;
+        }
+        let text = self.0.to_string();
+        let rest = text.trim_start().strip_prefix("`include")?.trim();
+        let (open, close) = match rest.chars().next()? {
+            '"' => ('"', '"'),
+            '<' => ('<', '>'),
+            _ => return None,
+        };
+        let path = rest.strip_prefix(open)?;
+        path.find(close).map(|end| path[..end].to_string())
+    }
+}
+
+impl HasName for ParameterPortDeclaration {
+    fn name_token(&self) -> Option<SyntaxToken> {
+        self.declarator().and_then(|declarator| declarator.name_token())
+            .or_else(|| self.type_assignment().and_then(|assignment| assignment.name_token()))
+    }
+}
+
+impl ModuleDeclaration {
+    pub fn parameter_port_list(&self) -> Option<ParameterPortList> {
+        child(&self.0)
+    }
+
+    /// Parameters of the header, in order
+    pub fn parameter_ports(&self) -> impl Iterator<Item = ParameterPortDeclaration> {
+        self.parameter_port_list().into_iter().flat_map(|list| list.declarations())
+    }
+
+    pub fn port_list(&self) -> Option<PortList> {
+        child(&self.0)
+    }
+
+    /// Ports of the header, in order
+    pub fn ports(&self) -> impl Iterator<Item = Port> {
+        self.port_list().into_iter().flat_map(|list| list.ports())
+    }
+
+    /// Header ports with their resolved direction, kind and data type.
+    ///
+    /// In an ANSI header a port without a direction inherits the previous
+    /// port's (the first defaults to `inout`), and one without a direction, kind
+    /// or type also inherits the previous port's kind and type. Names of a
+    /// non-ANSI header take them from the matching port declaration in the body.
+    pub fn port_signatures(&self) -> Vec<PortSignature> {
+        let ports: Vec<Port> = self.ports().collect();
+        if ports.first().is_some_and(Port::is_bare) {
+            return ports.into_iter().map(|port| self.declared_signature(port)).collect();
+        }
+
+        let mut signatures: Vec<PortSignature> = Vec::with_capacity(ports.len());
+        for port in ports {
+            let signature = match signatures.last() {
+                Some(previous) if port.is_bare() => PortSignature { port, ..previous.clone() },
+                previous => PortSignature {
+                    direction: port.direction()
+                        .or_else(|| previous.and_then(|previous| previous.direction))
+                        .or(Some(PortDirection::Inout)),
+                    kind: port.kind(),
+                    data_type: port.data_type(),
+                    interface: port.interface(),
+                    port,
+                },
+            };
+            signatures.push(signature);
+        }
+        signatures
+    }
+
+    fn declared_signature(&self, port: Port) -> PortSignature {
+        let name = port.name();
+        let declaration = self.port_declarations().find(|declaration| {
+            declaration.declarators().any(|declarator| declarator.name().is_some() && declarator.name() == name)
+        });
+        match declaration {
+            Some(declaration) => PortSignature {
+                direction: declaration.direction(),
+                kind: declaration.kind(),
+                data_type: declaration.data_type(),
+                interface: declaration.interface(),
+                port,
+            },
+            None => PortSignature { port, direction: None, kind: None, data_type: None, interface: None },
+        }
+    }
+
+    pub fn port_declarations(&self) -> impl Iterator<Item = PortDeclaration> {
+        children(&self.0)
+    }
+
+    /// Module items between the header and `endmodule`
+    pub fn items(&self) -> impl Iterator<Item = SyntaxNode> {
+        self.0.children().filter(|node| !matches!(node.kind(), SyntaxKind::PortList | SyntaxKind::EndLabel))
+    }
+
+    pub fn instantiations(&self) -> impl Iterator<Item = ModuleInstantiation> {
+        children(&self.0)
+    }
+
+    pub fn continuous_assigns(&self) -> impl Iterator<Item = ContinuousAssign> {
+        children(&self.0)
+    }
+
+    pub fn always_constructs(&self) -> impl Iterator<Item = AlwaysConstruct> {
+        children(&self.0)
+    }
+
+    pub fn typedefs(&self) -> impl Iterator<Item = TypedefDeclaration> {
+        children(&self.0)
+    }
+
+    pub fn net_declarations(&self) -> impl Iterator<Item = NetDeclaration> {
+        children(&self.0)
+    }
+
+    pub fn data_declarations(&self) -> impl Iterator<Item = DataDeclaration> {
+        children(&self.0)
+    }
+
+    /// Module whose body contains a protected envelope; only its ports are visible
+    pub fn is_black_box(&self) -> bool {
+        token(&self.0, SyntaxKind::ProtectedEnvelope).is_some()
+    }
+}
+
+impl PortList {
+    pub fn ports(&self) -> impl Iterator<Item = Port> {
+        children(&self.0)
+    }
+}
+
+impl Port {
+    /// Explicit direction; `None` for non-ANSI names and ports inheriting it
+    pub fn direction(&self) -> Option<PortDirection> {
+        direction(&self.0)
+    }
+
+    /// Explicit net type or `var`
+    pub fn kind(&self) -> Option<PortKind> {
+        port_kind(&self.0)
+    }
+
+    pub fn data_type(&self) -> Option<DataType> {
+        child(&self.0)
+    }
+
+    pub fn interface(&self) -> Option<InterfacePortHeader> {
+        child(&self.0)
+    }
+
+    pub fn unpacked_dimensions(&self) -> impl Iterator<Item = UnpackedDimension> {
+        children(&self.0)
+    }
+
+    /// Default value after `=`, or the expression of an explicit `.name(expr)` port
+    pub fn default_value(&self) -> Option<SyntaxNode> {
+        self.0.children().find(|node| {
+            !matches!(
+                node.kind(),
+                SyntaxKind::DataType | SyntaxKind::InterfacePortHeader | SyntaxKind::UnpackedDimension
+            )
+        })
+    }
+
+    /// An explicit `.name(expr)` port
+    pub fn is_explicit(&self) -> bool {
+        first_token(&self.0).is_some_and(|token| token.kind() == SyntaxKind::Dot)
+    }
+
+    /// A port name without direction, kind, type or dimensions
+    fn is_bare(&self) -> bool {
+        self.0.children().next().is_none() && first_token(&self.0).is_some_and(|token| token.kind() == SyntaxKind::Identifier)
+    }
+}
+
+impl InterfacePortHeader {
+    /// Interface name; `None` for a generic `interface` port
+    pub fn interface_name(&self) -> Option<String> {
+        first_token(&self.0)
+            .filter(|token| token.kind() == SyntaxKind::Identifier)
+            .map(|token| token.text().to_string())
+    }
+
+    pub fn modport(&self) -> Option<String> {
+        self.0.children_with_tokens()
+            .filter_map(|element| element.into_token())
+            .skip_while(|token| token.kind() != SyntaxKind::Dot)
+            .find(|token| token.kind() == SyntaxKind::Identifier)
+            .map(|token| token.text().to_string())
+    }
+}
+
+impl DataType {
+    pub fn packed_dimensions(&self) -> impl Iterator<Item = PackedDimension> {
+        children(&self.0)
+    }
+
+    pub fn struct_union(&self) -> Option<StructUnionType> {
+        child(&self.0)
+    }
+
+    pub fn enum_type(&self) -> Option<EnumType> {
+        child(&self.0)
+    }
+}
+
+impl StructUnionType {
+    pub fn is_union(&self) -> bool {
+        first_token(&self.0).is_some_and(|token| token.kind() == SyntaxKind::Union)
+    }
+
+    pub fn is_packed(&self) -> bool {
+        token(&self.0, SyntaxKind::Packed).is_some()
+    }
+
+    pub fn members(&self) -> impl Iterator<Item = StructMember> {
+        children(&self.0)
+    }
+}
+
+impl StructMember {
+    pub fn data_type(&self) -> Option<DataType> {
+        child(&self.0)
+    }
+
+    pub fn declarators(&self) -> impl Iterator<Item = Declarator> {
+        children(&self.0)
+    }
+}
+
+impl EnumType {
+    /// Explicit base type; `None` for the default `int`
+    pub fn base_type(&self) -> Option<DataType> {
+        child(&self.0)
+    }
+
+    pub fn members(&self) -> impl Iterator<Item = EnumMember> {
+        children(&self.0)
+    }
+}
+
+impl EnumMember {
+    /// Expressions of a `name[N]` or `name[N:M]` range
+    pub fn range(&self) -> impl Iterator<Item = SyntaxNode> {
+        let has_range = token(&self.0, SyntaxKind::LeftBracket).is_some();
+        let close = token(&self.0, SyntaxKind::RightBracket).map(|token| token.text_range().end());
+        self.0.children().filter(move |node| has_range && close.is_some_and(|end| node.text_range().end() <= end))
+    }
+
+    /// Explicit value after `=`
+    pub fn value(&self) -> Option<SyntaxNode> {
+        token(&self.0, SyntaxKind::Assign).and_then(|assign| {
+            self.0.children().find(|node| node.text_range().start() >= assign.text_range().end())
+        })
+    }
+}
+
+/// What an unpacked dimension declares
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DimensionShape {
+    /// `[7:0]`
+    Range,
+    /// `[8]`
+    Size,
+    /// `[]`
+    Dynamic,
+    /// `[*]` or `[key_type]`
+    Associative,
+    /// `[$]` or `[$:bound]`
+    Queue,
+}
+
+impl UnpackedDimension {
+    pub fn shape(&self) -> DimensionShape {
+        let first = self.0.children().next();
+        match first.as_ref().map(|node| node.kind()) {
+            None if token(&self.0, SyntaxKind::Multiply).is_some() => DimensionShape::Associative,
+            None => DimensionShape::Dynamic,
+            Some(SyntaxKind::DataType) => DimensionShape::Associative,
+            Some(SyntaxKind::Literal) if first.as_ref().is_some_and(|node| node.to_string().trim() == "$") => {
+                DimensionShape::Queue
+            }
+            Some(_) if token(&self.0, SyntaxKind::Colon).is_some() => DimensionShape::Range,
+            Some(_) => DimensionShape::Size,
+        }
+    }
+}
+
+impl PortDeclaration {
+    pub fn direction(&self) -> Option<PortDirection> {
+        direction(&self.0)
+    }
+
+    pub fn kind(&self) -> Option<PortKind> {
+        port_kind(&self.0)
+    }
+
+    pub fn data_type(&self) -> Option<DataType> {
+        child(&self.0)
+    }
+
+    pub fn interface(&self) -> Option<InterfacePortHeader> {
+        child(&self.0)
+    }
+
+    pub fn declarators(&self) -> impl Iterator<Item = Declarator> {
+        children(&self.0)
+    }
+}
+
+impl NetDeclaration {
+    /// The net type keyword, e.g. `Wire` or `TriReg`
+    pub fn net_type(&self) -> Option<SyntaxKind> {
+        first_token(&self.0).map(|token| token.kind())
+    }
+
+    pub fn drive_strength(&self) -> Option<DriveStrength> {
+        child(&self.0)
+    }
+
+    pub fn delay(&self) -> Option<Delay3> {
+        child(&self.0)
+    }
+
+    pub fn data_type(&self) -> Option<DataType> {
+        child(&self.0)
+    }
+
+    pub fn declarators(&self) -> impl Iterator<Item = Declarator> {
+        children(&self.0)
+    }
+}
+
+impl DataDeclaration {
+    pub fn data_type(&self) -> Option<DataType> {
+        child(&self.0)
+    }
+
+    pub fn declarators(&self) -> impl Iterator<Item = Declarator> {
+        children(&self.0)
+    }
+}
+
+impl DriveStrength {
+    /// The two strength keywords, in source order
+    pub fn strengths(&self) -> Vec<SyntaxKind> {
+        self.0.children_with_tokens()
+            .filter_map(|element| element.into_token())
+            .map(|token| token.kind())
+            .filter(|kind| !kind.is_trivia() && !matches!(kind, SyntaxKind::LeftParen | SyntaxKind::RightParen | SyntaxKind::Comma))
+            .collect()
+    }
+}
+
+impl Delay3 {
+    /// The rise, fall and turn-off delays given
+    pub fn values(&self) -> impl Iterator<Item = SyntaxNode> {
+        self.0.children()
+    }
+}
+
+impl TypedefDeclaration {
+    /// The defined type; `None` for a forward typedef
+    pub fn data_type(&self) -> Option<DataType> {
+        child(&self.0)
+    }
+
+    pub fn is_forward(&self) -> bool {
+        self.data_type().is_none()
+    }
+
+    pub fn unpacked_dimensions(&self) -> impl Iterator<Item = UnpackedDimension> {
+        children(&self.0)
+    }
+}
+
+impl ParameterDeclaration {
+    /// Whether this is a `localparam`
+    pub fn is_local(&self) -> bool {
+        first_token(&self.0).is_some_and(|token| token.kind() == SyntaxKind::LocalParam)
+    }
+
+    pub fn declarators(&self) -> impl Iterator<Item = Declarator> {
+        children(&self.0)
+    }
+}
+
+impl ParameterDeclaration {
+    /// Assignments of a `parameter type` declaration
+    pub fn type_assignments(&self) -> impl Iterator<Item = TypeAssignment> {
+        children(&self.0)
+    }
+}
+
+impl ParameterPortList {
+    pub fn declarations(&self) -> impl Iterator<Item = ParameterPortDeclaration> {
+        children(&self.0)
+    }
+}
+
+impl ParameterPortDeclaration {
+    /// Explicit `localparam`; an entry without a keyword continues the previous one
+    pub fn is_local(&self) -> bool {
+        first_token(&self.0).is_some_and(|token| token.kind() == SyntaxKind::LocalParam)
+    }
+
+    /// Whether this declares a type parameter
+    pub fn is_type(&self) -> bool {
+        self.type_assignment().is_some()
+    }
+
+    pub fn data_type(&self) -> Option<DataType> {
+        child(&self.0)
+    }
+
+    /// The value parameter assignment
+    pub fn declarator(&self) -> Option<Declarator> {
+        child(&self.0)
+    }
+
+    pub fn type_assignment(&self) -> Option<TypeAssignment> {
+        child(&self.0)
+    }
+}
+
+impl TypeAssignment {
+    pub fn default_type(&self) -> Option<DataType> {
+        child(&self.0)
+    }
+}
+
+impl Declarator {
+    /// The initializer after `=`
+    pub fn initializer(&self) -> Option<SyntaxNode> {
+        self.0.children().find(|node| node.kind() != SyntaxKind::UnpackedDimension)
+    }
+}
+
+impl ContinuousAssign {
+    pub fn assignments(&self) -> impl Iterator<Item = NetAssignment> {
+        children(&self.0)
+    }
+}
+
+impl NetAssignment {
+    pub fn lhs(&self) -> Option<SyntaxNode> {
+        self.0.children().next()
+    }
+
+    pub fn rhs(&self) -> Option<SyntaxNode> {
+        self.0.children().nth(1)
+    }
+}
+
+impl AlwaysConstruct {
+    /// `Always`, `AlwaysComb`, `AlwaysFF` or `AlwaysLatch`
+    pub fn keyword(&self) -> Option<SyntaxKind> {
+        first_token(&self.0).map(|token| token.kind())
+    }
+
+    pub fn statement(&self) -> Option<SyntaxNode> {
+        self.0.children().next()
+    }
+
+    /// Event control in front of the statement, e.g. `@(posedge clk)`
+    pub fn event_control(&self) -> Option<EventControl> {
+        self.statement()
+            .filter(|statement| statement.kind() == SyntaxKind::TimingControlStatement)
+            .and_then(|statement| child(&statement))
+    }
+}
+
+impl EventControl {
+    /// Events of `@( ... )`; empty for `@*` and `@(*)`
+    pub fn events(&self) -> impl Iterator<Item = EventExpression> {
+        children(&self.0)
+    }
+
+    /// Whether this is the implicit `@*` or `@(*)`
+    pub fn is_implicit(&self) -> bool {
+        token(&self.0, SyntaxKind::Multiply).is_some()
+    }
+}
+
+impl EventExpression {
+    /// `PosEdge`, `NegEdge` or `Edge`
+    pub fn edge(&self) -> Option<SyntaxKind> {
+        first_token(&self.0)
+            .map(|token| token.kind())
+            .filter(|kind| matches!(kind, SyntaxKind::PosEdge | SyntaxKind::NegEdge | SyntaxKind::Edge))
+    }
+
+    pub fn expression(&self) -> Option<SyntaxNode> {
+        self.0.children().next()
+    }
+
+    /// The guard after `iff`
+    pub fn guard(&self) -> Option<SyntaxNode> {
+        self.0.children().nth(1)
+    }
+}
+
+/// Name of a block: `begin : name` or `fork : name`
+fn block_label(node: &SyntaxNode) -> Option<String> {
+    node.children()
+        .find(|node| node.kind() == SyntaxKind::EndLabel)
+        .and_then(|label| token(&label, SyntaxKind::Identifier))
+        .map(|token| token.text().to_string())
+}
+
+/// Declarations and statements of a block, without its labels
+fn block_items(node: &SyntaxNode) -> impl Iterator<Item = SyntaxNode> {
+    node.children().filter(|node| node.kind() != SyntaxKind::EndLabel)
+}
+
+impl SeqBlock {
+    pub fn label(&self) -> Option<String> {
+        block_label(&self.0)
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = SyntaxNode> {
+        block_items(&self.0)
+    }
+}
+
+impl ParBlock {
+    pub fn label(&self) -> Option<String> {
+        block_label(&self.0)
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = SyntaxNode> {
+        block_items(&self.0)
+    }
+
+    /// `Join`, `JoinAny` or `JoinNone`
+    pub fn join_keyword(&self) -> Option<SyntaxKind> {
+        self.0.children_with_tokens()
+            .filter_map(|element| element.into_token())
+            .map(|token| token.kind())
+            .find(|kind| matches!(kind, SyntaxKind::Join | SyntaxKind::JoinAny | SyntaxKind::JoinNone))
+    }
+}
+
+/// `unique`, `unique0` or `priority` in front of an `if` or `case`
+fn unique_priority(node: &SyntaxNode) -> Option<SyntaxKind> {
+    first_token(node)
+        .map(|token| token.kind())
+        .filter(|kind| matches!(kind, SyntaxKind::Unique | SyntaxKind::Unique0 | SyntaxKind::Priority))
+}
+
+impl IfStatement {
+    pub fn qualifier(&self) -> Option<SyntaxKind> {
+        unique_priority(&self.0)
+    }
+
+    pub fn condition(&self) -> Option<SyntaxNode> {
+        self.0.children().next()
+    }
+
+    pub fn then_branch(&self) -> Option<SyntaxNode> {
+        self.0.children().nth(1)
+    }
+
+    pub fn else_branch(&self) -> Option<SyntaxNode> {
+        self.0.children().nth(2)
+    }
+}
+
+impl CaseStatement {
+    pub fn qualifier(&self) -> Option<SyntaxKind> {
+        unique_priority(&self.0)
+    }
+
+    /// `Case`, `CaseX` or `CaseZ`
+    pub fn keyword(&self) -> Option<SyntaxKind> {
+        self.0.children_with_tokens()
+            .filter_map(|element| element.into_token())
+            .map(|token| token.kind())
+            .find(|kind| matches!(kind, SyntaxKind::Case | SyntaxKind::CaseX | SyntaxKind::CaseZ))
+    }
+
+    /// Whether this is a `case ... inside`
+    pub fn is_inside(&self) -> bool {
+        token(&self.0, SyntaxKind::Inside).is_some()
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = CaseItem> {
+        children(&self.0)
+    }
+}
+
+impl CaseItem {
+    pub fn is_default(&self) -> bool {
+        token(&self.0, SyntaxKind::Default).is_some()
+    }
+}
+
+impl ModuleInstantiation {
+    /// Name of the instantiated module
+    pub fn module_name(&self) -> Option<String> {
+        self.module_name_token().map(|token| token.text().to_string())
+    }
+
+    pub fn module_name_token(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::Identifier)
+    }
+
+    pub fn parameter_value_assignment(&self) -> Option<ParameterValueAssignment> {
+        child(&self.0)
+    }
+
+    pub fn instances(&self) -> impl Iterator<Item = HierarchicalInstance> {
+        children(&self.0)
+    }
+}
+
+impl ParameterValueAssignment {
+    pub fn named_assignments(&self) -> impl Iterator<Item = NamedParameterAssignment> {
+        children(&self.0)
+    }
+
+    pub fn ordered_assignments(&self) -> impl Iterator<Item = OrderedParameterAssignment> {
+        children(&self.0)
+    }
+}
+
+impl NamedParameterAssignment {
+    pub fn parameter_name(&self) -> Option<String> {
+        token(&self.0, SyntaxKind::Identifier).map(|token| token.text().to_string())
+    }
+
+    /// The assigned expression or data type; `None` for `.name()`
+    pub fn value(&self) -> Option<SyntaxNode> {
+        self.0.children().next()
+    }
+}
+
+impl OrderedParameterAssignment {
+    pub fn value(&self) -> Option<SyntaxNode> {
+        self.0.children().next()
+    }
+}
+
+/// One entry of an instance's port connection list
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PortConnection {
+    Named(NamedPortConnection),
+    Implicit(ImplicitPortConnection),
+    Wildcard(WildcardPortConnection),
+    Ordered(OrderedPortConnection),
+}
+
+impl AstNode for PortConnection {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        NamedPortConnection::can_cast(kind)
+            || ImplicitPortConnection::can_cast(kind)
+            || WildcardPortConnection::can_cast(kind)
+            || OrderedPortConnection::can_cast(kind)
+    }
+
+    fn cast(node: SyntaxNode) -> Option<Self> {
+        match node.kind() {
+            SyntaxKind::NamedPortConnection => Some(Self::Named(NamedPortConnection(node))),
+            SyntaxKind::ImplicitPortConnection => Some(Self::Implicit(ImplicitPortConnection(node))),
+            SyntaxKind::WildcardPortConnection => Some(Self::Wildcard(WildcardPortConnection(node))),
+            SyntaxKind::OrderedPortConnection => Some(Self::Ordered(OrderedPortConnection(node))),
+            _ => None,
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        match self {
+            Self::Named(connection) => connection.syntax(),
+            Self::Implicit(connection) => connection.syntax(),
+            Self::Wildcard(connection) => connection.syntax(),
+            Self::Ordered(connection) => connection.syntax(),
+        }
+    }
+}
+
+impl HierarchicalInstance {
+    fn connection_list(&self) -> Option<SyntaxNode> {
+        self.0.children().find(|node| node.kind() == SyntaxKind::PortConnectionList)
+    }
+
+    /// Dimensions of an instance array such as `u[3:0]`
+    pub fn unpacked_dimensions(&self) -> impl Iterator<Item = UnpackedDimension> {
+        children(&self.0)
+    }
+
+    /// All connections, in order
+    pub fn connections(&self) -> impl Iterator<Item = PortConnection> {
+        self.connection_list().into_iter().flat_map(|list| children(&list).collect::<Vec<_>>())
+    }
+
+    pub fn implicit_connections(&self) -> impl Iterator<Item = ImplicitPortConnection> {
+        self.connection_list().into_iter().flat_map(|list| children(&list).collect::<Vec<_>>())
+    }
+
+    /// Whether the list contains `.*`
+    pub fn has_wildcard_connection(&self) -> bool {
+        self.connections().any(|connection| matches!(connection, PortConnection::Wildcard(_)))
+    }
+
+    pub fn named_connections(&self) -> impl Iterator<Item = NamedPortConnection> {
+        self.connection_list().into_iter().flat_map(|list| children(&list).collect::<Vec<_>>())
+    }
+
+    pub fn ordered_connections(&self) -> impl Iterator<Item = OrderedPortConnection> {
+        self.connection_list().into_iter().flat_map(|list| children(&list).collect::<Vec<_>>())
+    }
+}
+
+impl NamedPortConnection {
+    pub fn port_name(&self) -> Option<String> {
+        token(&self.0, SyntaxKind::Identifier).map(|token| token.text().to_string())
+    }
+
+    /// The connected expression; `None` for an explicitly unconnected `.name()`
+    pub fn expression(&self) -> Option<SyntaxNode> {
+        self.0.children().next()
+    }
+}
+
+impl ImplicitPortConnection {
+    pub fn port_name(&self) -> Option<String> {
+        token(&self.0, SyntaxKind::Identifier).map(|token| token.text().to_string())
+    }
+}
+
+impl OrderedPortConnection {
+    /// The connected expression; `None` for a blank, unconnected position
+    pub fn expression(&self) -> Option<SyntaxNode> {
+        self.0.children().next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_tokens_with_spans;
+    use svcs_lexer::create_default_lexer;
+    use svcs_source::SourceDb;
+
+    fn parse(input: &str) -> crate::Cst {
+        let mut db = SourceDb::new();
+        let file = db.add_file("test.sv", input);
+        let tokens = create_default_lexer(input, "test.sv".to_string()).unwrap().tokenize().unwrap();
+        parse_tokens_with_spans(db.file(file), &tokens).unwrap()
+    }
+
+    #[test]
+    fn test_module_and_ports() {
+        let cst = parse(
+            "module full_adder(input a, b, output logic cout);\n\
+             wire carry1, carry2;\n\
+             assign cout = carry1 | carry2;\n\
+             always_comb x = a;\n\
+             endmodule : full_adder",
+        );
+        let file = SourceFile::cast(cst.syntax()).unwrap();
+        let module = file.modules().next().unwrap();
+        assert_eq!(module.name().as_deref(), Some("full_adder"));
+
+        let ports: Vec<_> = module.ports().map(|port| (port.name().unwrap(), port.direction())).collect();
+        assert_eq!(ports, [
+            ("a".to_string(), Some(PortDirection::Input)),
+            ("b".to_string(), None),
+            ("cout".to_string(), Some(PortDirection::Output)),
+        ]);
+        assert!(module.ports().nth(2).unwrap().data_type().is_some());
+
+        let assign = module.continuous_assigns().next().unwrap().assignments().next().unwrap();
+        assert_eq!(assign.lhs().unwrap().kind(), SyntaxKind::NameRef);
+        assert_eq!(assign.rhs().unwrap().kind(), SyntaxKind::BinaryExpression);
+        assert_eq!(module.always_constructs().next().unwrap().keyword(), Some(SyntaxKind::AlwaysComb));
+        assert_eq!(module.items().count(), 3);
+    }
+
+    #[test]
+    fn test_port_signatures() {
+        let cst = parse(
+            "module m(input logic [7:0] a, b, output reg signed [3:0] q, bus_if.master m, c, \
+             ref var int r [2], input wire d = 1'b0); endmodule",
+        );
+        let module = SourceFile::cast(cst.syntax()).unwrap().modules().next().unwrap();
+        let signatures = module.port_signatures();
+        let summary: Vec<_> = signatures.iter()
+            .map(|sig| (sig.port.name().unwrap(), sig.direction, sig.kind, sig.data_type.as_ref().map(|t| t.syntax().to_string())))
+            .collect();
+        assert_eq!(summary, [
+            ("a".to_string(), Some(PortDirection::Input), None, Some("logic [7:0]".to_string())),
+            ("b".to_string(), Some(PortDirection::Input), None, Some("logic [7:0]".to_string())),
+            ("q".to_string(), Some(PortDirection::Output), None, Some("reg signed [3:0]".to_string())),
+            ("m".to_string(), Some(PortDirection::Output), None, None),
+            ("c".to_string(), Some(PortDirection::Output), None, None),
+            ("r".to_string(), Some(PortDirection::Ref), Some(PortKind::Var), Some("int".to_string())),
+            ("d".to_string(), Some(PortDirection::Input), Some(PortKind::Net(SyntaxKind::Wire)), None),
+        ]);
+        let interface = signatures[4].interface.as_ref().unwrap();
+        assert_eq!(interface.interface_name().as_deref(), Some("bus_if"));
+        assert_eq!(interface.modport().as_deref(), Some("master"));
+        assert_eq!(signatures[0].data_type.as_ref().unwrap().packed_dimensions().count(), 1);
+        assert_eq!(signatures[5].port.unpacked_dimensions().count(), 1);
+        assert_eq!(signatures[6].port.default_value().unwrap().kind(), SyntaxKind::Literal);
+    }
+
+    #[test]
+    fn test_non_ansi_port_signatures() {
+        let cst = parse("module m(a, b, y); input a; input [3:0] b; output reg y; endmodule");
+        let module = SourceFile::cast(cst.syntax()).unwrap().modules().next().unwrap();
+        let signatures = module.port_signatures();
+        let directions: Vec<_> = signatures.iter().map(|sig| sig.direction).collect();
+        assert_eq!(directions, [Some(PortDirection::Input), Some(PortDirection::Input), Some(PortDirection::Output)]);
+        assert!(signatures[0].data_type.is_none());
+        assert_eq!(signatures[1].data_type.as_ref().unwrap().syntax().to_string(), "[3:0]");
+        assert_eq!(signatures[2].data_type.as_ref().unwrap().syntax().to_string(), "reg");
+    }
+
+    #[test]
+    fn test_procedural_blocks() {
+        let cst = parse(
+            "module m;\n\
+             always_ff @(posedge clk or negedge rst_n) begin : seq\n\
+               if (!rst_n) q <= '0; else q <= d;\n\
+             end\n\
+             always @* unique case (s) 0: y = a; default: y = b; endcase\n\
+             initial fork a = 1; join_none\n\
+             endmodule",
+        );
+        let module = SourceFile::cast(cst.syntax()).unwrap().modules().next().unwrap();
+        let always: Vec<_> = module.always_constructs().collect();
+        let events = always[0].event_control().unwrap();
+        let edges: Vec<_> = events.events().map(|event| event.edge()).collect();
+        assert_eq!(edges, [Some(SyntaxKind::PosEdge), Some(SyntaxKind::NegEdge)]);
+        let block = SeqBlock::cast(events.syntax().next_sibling().unwrap()).unwrap();
+        assert_eq!(block.label().as_deref(), Some("seq"));
+        let branch = IfStatement::cast(block.items().next().unwrap()).unwrap();
+        assert_eq!(branch.then_branch().unwrap().kind(), SyntaxKind::NonblockingAssignment);
+        assert!(branch.else_branch().is_some());
+
+        assert!(always[1].event_control().unwrap().is_implicit());
+        let case = CaseStatement::cast(always[1].event_control().unwrap().syntax().next_sibling().unwrap()).unwrap();
+        assert_eq!((case.qualifier(), case.keyword()), (Some(SyntaxKind::Unique), Some(SyntaxKind::Case)));
+        assert_eq!(case.items().filter(CaseItem::is_default).count(), 1);
+
+        let fork = module.syntax().descendants().find_map(ParBlock::cast).unwrap();
+        assert_eq!(fork.join_keyword(), Some(SyntaxKind::JoinNone));
+        assert_eq!(fork.items().count(), 1);
+    }
+
+    #[test]
+    fn test_declarations() {
+        let cst = parse(
+            "module m;\n\
+             typedef enum logic [1:0] {IDLE, RUN = 2'd1, S[2]} state_t;\n\
+             typedef struct packed { logic [3:0] a; bit b; } pair_t;\n\
+             typedef fwd_t;\n\
+             wire (strong0, weak1) #(1, 2) w;\n\
+             logic [7:0] mem [0:255], q [$], aa [string], d [];\n\
+             endmodule",
+        );
+        let module = SourceFile::cast(cst.syntax()).unwrap().modules().next().unwrap();
+        let typedefs: Vec<_> = module.typedefs().collect();
+        assert_eq!(typedefs[0].name().as_deref(), Some("state_t"));
+        let state = typedefs[0].data_type().unwrap().enum_type().unwrap();
+        assert_eq!(state.base_type().unwrap().syntax().to_string(), "logic [1:0]");
+        let members: Vec<_> = state.members()
+            .map(|member| (member.name().unwrap(), member.value().map(|v| v.to_string()), member.range().count()))
+            .collect();
+        assert_eq!(members, [
+            ("IDLE".to_string(), None, 0),
+            ("RUN".to_string(), Some("2'd1".to_string()), 0),
+            ("S".to_string(), None, 1),
+        ]);
+
+        let pair = typedefs[1].data_type().unwrap().struct_union().unwrap();
+        assert!(pair.is_packed() && !pair.is_union());
+        let fields: Vec<_> = pair.members().flat_map(|member| member.declarators()).filter_map(|d| d.name()).collect();
+        assert_eq!(fields, ["a", "b"]);
+        assert!(typedefs[2].is_forward());
+
+        let net = module.net_declarations().next().unwrap();
+        assert_eq!(net.net_type(), Some(SyntaxKind::Wire));
+        assert_eq!(net.drive_strength().unwrap().strengths(), [SyntaxKind::Strong0, SyntaxKind::Weak1]);
+        assert_eq!(net.delay().unwrap().values().count(), 2);
+
+        let shapes: Vec<_> = module.data_declarations().next().unwrap().declarators()
+            .flat_map(|declarator| declarator.syntax().children().filter_map(UnpackedDimension::cast).collect::<Vec<_>>())
+            .map(|dimension| dimension.shape())
+            .collect();
+        assert_eq!(shapes, [
+            DimensionShape::Range,
+            DimensionShape::Queue,
+            DimensionShape::Associative,
+            DimensionShape::Dynamic,
+        ]);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let cst = parse(
+            "module fifo #(parameter int DEPTH = 16, type T = logic) (input T d);\n\
+             fifo #(.DEPTH(32)) u_fifo (.d(x));\n\
+             endmodule",
+        );
+        let module = SourceFile::cast(cst.syntax()).unwrap().modules().next().unwrap();
+        let params: Vec<_> = module.parameter_ports().map(|param| (param.name().unwrap(), param.is_type())).collect();
+        assert_eq!(params, [("DEPTH".to_string(), false), ("T".to_string(), true)]);
+        let depth = module.parameter_ports().next().unwrap();
+        assert_eq!(depth.declarator().unwrap().initializer().unwrap().to_string(), "16");
+        assert_eq!(module.ports().count(), 1);
+
+        let inst = module.instantiations().next().unwrap();
+        assert_eq!(inst.module_name().as_deref(), Some("fifo"));
+        let assignment = inst.parameter_value_assignment().unwrap().named_assignments().next().unwrap();
+        assert_eq!(assignment.parameter_name().as_deref(), Some("DEPTH"));
+        assert_eq!(assignment.value().unwrap().to_string(), "32");
+        assert_eq!(inst.instances().next().unwrap().name().as_deref(), Some("u_fifo"));
+    }
+
+    #[test]
+    fn test_instantiation() {
+        let cst = parse("module top; half_adder ha1(.a(x), .b(), y); endmodule");
+        let module = SourceFile::cast(cst.syntax()).unwrap().modules().next().unwrap();
+        let inst = module.instantiations().next().unwrap();
+        assert_eq!(inst.module_name().as_deref(), Some("half_adder"));
+
+        let instance = inst.instances().next().unwrap();
+        assert_eq!(instance.name().as_deref(), Some("ha1"));
+        let named: Vec<_> = instance.named_connections()
+            .map(|conn| (conn.port_name().unwrap(), conn.expression().map(|e| e.kind())))
+            .collect();
+        assert_eq!(named, [("a".to_string(), Some(SyntaxKind::NameRef)), ("b".to_string(), None)]);
+        assert_eq!(instance.ordered_connections().count(), 1);
+        assert!(ModuleDeclaration::cast(inst.syntax().clone()).is_none());
+    }
+
+    #[test]
+    fn test_connection_styles() {
+        let cst = parse("module top; dff u1[1:0] (.d, .q(), .*), u2(a, , b); endmodule");
+        let module = SourceFile::cast(cst.syntax()).unwrap().modules().next().unwrap();
+        let instances: Vec<_> = module.instantiations().next().unwrap().instances().collect();
+        assert_eq!(instances.len(), 2);
+        assert_eq!(instances[0].unpacked_dimensions().count(), 1);
+        assert!(instances[0].has_wildcard_connection());
+        assert_eq!(instances[0].implicit_connections().next().unwrap().port_name().as_deref(), Some("d"));
+        let kinds: Vec<_> = instances[0].connections().map(|connection| connection.syntax().kind()).collect();
+        assert_eq!(kinds, [
+            SyntaxKind::ImplicitPortConnection,
+            SyntaxKind::NamedPortConnection,
+            SyntaxKind::WildcardPortConnection,
+        ]);
+        let ordered: Vec<_> = instances[1].ordered_connections().map(|c| c.expression().map(|e| e.to_string())).collect();
+        assert_eq!(ordered, [Some("a".to_string()), None, Some("b".to_string())]);
+        assert!(!instances[1].has_wildcard_connection());
+    }
+
+    #[test]
+    fn test_include_directives() {
+        let cst = parse("`include \"defs.svh\"\n`timescale 1ns/1ps\nmodule m;\n`include <cells/lib.svh>\nendmodule\n");
+        let directives: Vec<_> = SourceFile::cast(cst.syntax()).unwrap().directives().collect();
+        let names: Vec<_> = directives.iter().filter_map(Directive::name).collect();
+        assert_eq!(names, ["`include", "`timescale", "`include"]);
+        let includes: Vec<_> = directives.iter().filter_map(Directive::include_path).collect();
+        assert_eq!(includes, ["defs.svh", "cells/lib.svh"]);
+    }
+}
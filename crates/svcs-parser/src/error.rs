@@ -0,0 +1,65 @@
+//! Syntax errors reported by the parser.
+
+use crate::SyntaxKind;
+use std::fmt;
+use svcs_source::Span;
+
+/// What the parser was looking for when it failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    Token(SyntaxKind),
+    /// A construct described in words, e.g. "an expression"
+    Construct(&'static str),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Token(kind) => write!(f, "{}", kind),
+            Expected::Construct(description) => f.write_str(description),
+        }
+    }
+}
+
+/// One syntax error, located at the offending token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub expected: Expected,
+    /// Kind and text of the offending token; `None` at the end of the file
+    pub found: Option<(SyntaxKind, String)>,
+    /// File and byte range of the offending token, empty at the end of the file
+    pub span: Span,
+    /// 1-based line and column of `span.range.start`
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: expected {}, found ", self.line, self.column, self.expected)?;
+        match &self.found {
+            Some((kind, text)) => write!(f, "{} '{}'", kind, text),
+            None => f.write_str("end of file"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// All syntax errors of one file, in source order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseErrors(pub Vec<ParseError>);
+
+impl fmt::Display for ParseErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, error) in self.0.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseErrors {}
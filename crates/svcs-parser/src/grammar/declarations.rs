@@ -0,0 +1,560 @@
+//! Data types, net/variable/parameter declarations and subroutine declarations.
+
+use super::*;
+
+/// Keywords that start a data type
+pub(crate) const DATA_TYPE_KEYWORDS: &[Token] = &[
+    Token::Logic, Token::Bit, Token::Byte, Token::ShortInt, Token::Int, Token::LongInt,
+    Token::Integer, Token::Time, Token::Real, Token::ShortReal, Token::RealTime,
+    Token::String, Token::Chandle, Token::Event, Token::Reg, Token::Struct, Token::Union,
+    Token::Enum,
+];
+
+/// net_type keywords
+pub(crate) const NET_TYPE_KEYWORDS: &[Token] = &[
+    Token::Wire, Token::Tri, Token::TriAnd, Token::TriOr, Token::Tri0, Token::Tri1,
+    Token::TriReg, Token::WAnd, Token::WOr, Token::Supply0, Token::Supply1, Token::UWire,
+];
+
+/// strength0, strength1 and `highz0`/`highz1` keywords of a drive strength
+const DRIVE_STRENGTH_KEYWORDS: &[Token] = &[
+    Token::Supply0, Token::Strong0, Token::Pull0, Token::Weak0, Token::HighZ0,
+    Token::Supply1, Token::Strong1, Token::Pull1, Token::Weak1, Token::HighZ1,
+];
+
+/// charge_strength keywords
+const CHARGE_STRENGTH_KEYWORDS: &[Token] = &[Token::Small, Token::Medium, Token::Large];
+
+pub(crate) fn at_data_type(p: &Parser) -> bool {
+    p.at_any(DATA_TYPE_KEYWORDS) || at_user_type(p)
+}
+
+/// A type name followed by the name being declared: `t x` or `pkg::t x`
+fn at_user_type(p: &Parser) -> bool {
+    p.at(Token::Identifier)
+        && (p.nth_at(1, Token::Identifier)
+            || (p.nth_at(1, Token::Scope) && p.nth_at(2, Token::Identifier) && p.nth_at(3, Token::Identifier)))
+}
+
+/// Start of a data_declaration: a data type, possibly after qualifiers
+pub(crate) fn at_data_declaration(p: &Parser) -> bool {
+    p.at_any(&[Token::Const, Token::Static, Token::Automatic]) || at_data_type(p)
+}
+
+/// data_type ::= integer_vector_type [ signing ] { packed_dimension }
+///             | integer_atom_type [ signing ] | non_integer_type | string | chandle | event
+///             | struct_union [ packed [ signing ] ] { struct_union_member { struct_union_member } }
+///               { packed_dimension }
+///             | enum [ enum_base_type ] { enum_name_declaration { , enum_name_declaration } }
+///               { packed_dimension }
+///             | [ package_scope ] type_identifier { packed_dimension }
+pub(crate) fn data_type(p: &mut Parser) {
+    p.start_node(SyntaxKind::DataType);
+    if p.at(Token::Identifier) {
+        p.bump();
+        if p.at(Token::Scope) {
+            p.bump();
+            p.expect(Token::Identifier);
+        }
+    } else if p.at_any(&[Token::Struct, Token::Union]) {
+        struct_union_type(p);
+    } else if p.at(Token::Enum) {
+        enum_type(p);
+    } else if p.at_any(DATA_TYPE_KEYWORDS) {
+        p.bump();
+    } else {
+        p.error("a data type");
+    }
+    if p.at_any(&[Token::Signed, Token::Unsigned]) {
+        p.bump();
+    }
+    while p.at(Token::LeftBracket) {
+        packed_dimension(p);
+    }
+    p.finish_node();
+}
+
+/// data_type_or_implicit ::= data_type | [ signing ] { packed_dimension }
+/// Builds nothing when the type is entirely implicit.
+pub(crate) fn data_type_or_implicit(p: &mut Parser) {
+    if at_data_type(p) {
+        data_type(p);
+    } else if p.at_any(&[Token::Signed, Token::Unsigned, Token::LeftBracket]) {
+        p.start_node(SyntaxKind::DataType);
+        if p.at_any(&[Token::Signed, Token::Unsigned]) {
+            p.bump();
+        }
+        while p.at(Token::LeftBracket) {
+            packed_dimension(p);
+        }
+        p.finish_node();
+    }
+}
+
+/// packed_dimension ::= [ constant_range ]
+pub(crate) fn packed_dimension(p: &mut Parser) {
+    dimension(p, SyntaxKind::PackedDimension);
+}
+
+/// variable_dimension ::= unsized_dimension | unpacked_dimension | associative_dimension
+///                      | queue_dimension
+/// All build an `UnpackedDimension`: `[]`, `[7:0]`, `[8]`, `[*]`, `[string]`, `[$]` or `[$:15]`.
+pub(crate) fn unpacked_dimension(p: &mut Parser) {
+    if p.nth_at(1, Token::RightBracket) {
+        p.start_node(SyntaxKind::UnpackedDimension);
+        p.bump();
+        p.bump();
+        p.finish_node();
+    } else if p.nth_at(1, Token::Multiply) && p.nth_at(2, Token::RightBracket) {
+        p.start_node(SyntaxKind::UnpackedDimension);
+        p.bump();
+        p.bump();
+        p.bump();
+        p.finish_node();
+    } else if p.nth(1).is_some_and(|token| DATA_TYPE_KEYWORDS.contains(&token)) {
+        p.start_node(SyntaxKind::UnpackedDimension);
+        p.bump();
+        data_type(p);
+        p.expect(Token::RightBracket);
+        p.finish_node();
+    } else {
+        dimension(p, SyntaxKind::UnpackedDimension);
+    }
+}
+
+fn dimension(p: &mut Parser, kind: SyntaxKind) {
+    p.start_node(kind);
+    p.expect(Token::LeftBracket);
+    expression(p);
+    if p.eat(Token::Colon) {
+        expression(p);
+    }
+    p.expect(Token::RightBracket);
+    p.finish_node();
+}
+
+/// struct_union ::= struct | union
+/// struct_union_member ::= data_type list_of_variable_decl_assignments ;
+fn struct_union_type(p: &mut Parser) {
+    p.start_node(SyntaxKind::StructUnionType);
+    p.bump();
+    if p.eat(Token::Packed) && p.at_any(&[Token::Signed, Token::Unsigned]) {
+        p.bump();
+    }
+    p.expect(Token::LeftBrace);
+    while !p.at_end() && !p.at(Token::RightBrace) && !p.at_unit_end() {
+        if !at_data_type(p) {
+            p.error_and_recover("a struct member", &[Token::RightBrace]);
+            continue;
+        }
+        p.start_node(SyntaxKind::StructMember);
+        data_type(p);
+        list_of_declarators(p);
+        p.expect(Token::Semicolon);
+        p.finish_node();
+    }
+    p.expect(Token::RightBrace);
+    p.finish_node();
+}
+
+/// enum_base_type ::= integer_atom_type [ signing ] | integer_vector_type [ signing ] [ packed_dimension ]
+///                  | type_identifier [ packed_dimension ]
+fn enum_type(p: &mut Parser) {
+    p.start_node(SyntaxKind::EnumType);
+    p.bump();
+    if !p.at(Token::LeftBrace) {
+        data_type(p);
+    }
+    p.expect(Token::LeftBrace);
+    loop {
+        enum_member(p);
+        if !p.eat(Token::Comma) {
+            break;
+        }
+    }
+    p.expect(Token::RightBrace);
+    p.finish_node();
+}
+
+/// enum_name_declaration ::= identifier [ [ integral_number [ : integral_number ] ] ]
+///                           [ = constant_expression ]
+fn enum_member(p: &mut Parser) {
+    p.start_node(SyntaxKind::EnumMember);
+    p.expect(Token::Identifier);
+    if p.eat(Token::LeftBracket) {
+        expression(p);
+        if p.eat(Token::Colon) {
+            expression(p);
+        }
+        p.expect(Token::RightBracket);
+    }
+    if p.eat(Token::Assign) {
+        expression(p);
+    }
+    p.finish_node();
+}
+
+/// type_declaration ::= typedef data_type identifier { variable_dimension } ;
+///                    | typedef [ enum | struct | union | class ] identifier ;
+pub(crate) fn type_declaration(p: &mut Parser) {
+    p.start_node(SyntaxKind::TypedefDeclaration);
+    p.bump();
+    let forward = (p.at(Token::Identifier) && p.nth_at(1, Token::Semicolon))
+        || (p.at_any(&[Token::Enum, Token::Struct, Token::Union, Token::Class])
+            && p.nth_at(1, Token::Identifier)
+            && p.nth_at(2, Token::Semicolon));
+    if forward {
+        if !p.at(Token::Identifier) {
+            p.bump();
+        }
+        p.bump();
+    } else {
+        data_type(p);
+        p.expect(Token::Identifier);
+        while p.at(Token::LeftBracket) {
+            unpacked_dimension(p);
+        }
+    }
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+/// net_declaration ::= net_type [ drive_strength | charge_strength ] [ vectored | scalared ]
+///                     data_type_or_implicit [ delay3 ] list_of_net_decl_assignments ;
+pub(crate) fn net_declaration(p: &mut Parser) {
+    p.start_node(SyntaxKind::NetDeclaration);
+    p.bump();
+    if p.at(Token::LeftParen) {
+        strength(p);
+    }
+    if p.at_any(&[Token::Vectored, Token::Scalared]) {
+        p.bump();
+    }
+    data_type_or_implicit(p);
+    if p.at(Token::Hash) {
+        delay3(p);
+    }
+    list_of_declarators(p);
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+/// drive_strength ::= ( strength0 , strength1 ) | ( strength1 , strength0 ) | ...
+/// charge_strength ::= ( small ) | ( medium ) | ( large )
+pub(crate) fn strength(p: &mut Parser) {
+    if p.nth(1).is_some_and(|token| CHARGE_STRENGTH_KEYWORDS.contains(&token)) {
+        p.start_node(SyntaxKind::ChargeStrength);
+        p.bump();
+        p.bump();
+    } else {
+        p.start_node(SyntaxKind::DriveStrength);
+        p.bump();
+        for index in 0..2 {
+            if index > 0 {
+                p.expect(Token::Comma);
+            }
+            if p.at_any(DRIVE_STRENGTH_KEYWORDS) {
+                p.bump();
+            } else {
+                p.error("a strength");
+            }
+        }
+    }
+    p.expect(Token::RightParen);
+    p.finish_node();
+}
+
+/// delay3 ::= # delay_value | # ( mintypmax_expression [ , mintypmax_expression
+///            [ , mintypmax_expression ] ] )
+pub(crate) fn delay3(p: &mut Parser) {
+    p.start_node(SyntaxKind::Delay3);
+    p.bump();
+    if p.eat(Token::LeftParen) {
+        loop {
+            expression(p);
+            if !p.eat(Token::Comma) {
+                break;
+            }
+        }
+        p.expect(Token::RightParen);
+    } else {
+        delay_value(p);
+    }
+    p.finish_node();
+}
+
+/// data_declaration ::= [ const ] [ lifetime ] data_type list_of_variable_decl_assignments ;
+pub(crate) fn data_declaration(p: &mut Parser) {
+    p.start_node(SyntaxKind::DataDeclaration);
+    p.eat(Token::Const);
+    if p.at_any(&[Token::Static, Token::Automatic]) {
+        p.bump();
+    }
+    data_type(p);
+    list_of_declarators(p);
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+pub(crate) fn list_of_declarators(p: &mut Parser) {
+    loop {
+        declarator(p);
+        if !p.eat(Token::Comma) {
+            break;
+        }
+    }
+}
+
+/// variable_decl_assignment ::= identifier { unpacked_dimension } [ = expression ]
+pub(crate) fn declarator(p: &mut Parser) {
+    p.start_node(SyntaxKind::Declarator);
+    p.expect(Token::Identifier);
+    while p.at(Token::LeftBracket) {
+        unpacked_dimension(p);
+    }
+    if p.eat(Token::Assign) {
+        expression(p);
+    }
+    p.finish_node();
+}
+
+/// parameter_declaration ::= parameter data_type_or_implicit list_of_param_assignments ;
+///                         | parameter type list_of_type_assignments ;
+/// local_parameter_declaration ::= localparam ... ;
+pub(crate) fn parameter_declaration(p: &mut Parser) {
+    p.start_node(SyntaxKind::ParameterDeclaration);
+    p.bump();
+    if p.eat(Token::Type) {
+        loop {
+            type_assignment(p);
+            if !p.eat(Token::Comma) {
+                break;
+            }
+        }
+    } else {
+        data_type_or_implicit(p);
+        list_of_declarators(p);
+    }
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+/// type_assignment ::= identifier [ = data_type ]
+fn type_assignment(p: &mut Parser) {
+    p.start_node(SyntaxKind::TypeAssignment);
+    p.expect(Token::Identifier);
+    if p.eat(Token::Assign) {
+        data_type(p);
+    }
+    p.finish_node();
+}
+
+/// parameter_port_list ::= # ( [ parameter_port_declaration { , parameter_port_declaration } ] )
+pub(crate) fn parameter_port_list(p: &mut Parser) {
+    p.start_node(SyntaxKind::ParameterPortList);
+    p.bump();
+    p.expect(Token::LeftParen);
+    if !p.at(Token::RightParen) {
+        loop {
+            parameter_port_declaration(p);
+            if !p.eat(Token::Comma) {
+                break;
+            }
+        }
+    }
+    p.expect(Token::RightParen);
+    p.finish_node();
+}
+
+/// parameter_port_declaration ::= [ parameter | localparam ] data_type_or_implicit param_assignment
+///                              | [ parameter | localparam ] type type_assignment
+/// One node per comma-separated assignment; a declaration without a keyword or
+/// type continues the one before it.
+fn parameter_port_declaration(p: &mut Parser) {
+    p.start_node(SyntaxKind::ParameterPortDeclaration);
+    if p.at_any(&[Token::Parameter, Token::LocalParam]) {
+        p.bump();
+    }
+    if p.eat(Token::Type) {
+        type_assignment(p);
+    } else {
+        data_type_or_implicit(p);
+        declarator(p);
+    }
+    p.finish_node();
+}
+
+/// genvar_declaration ::= genvar identifier { , identifier } ;
+pub(crate) fn genvar_declaration(p: &mut Parser) {
+    p.start_node(SyntaxKind::GenvarDeclaration);
+    p.bump();
+    loop {
+        p.expect(Token::Identifier);
+        if !p.eat(Token::Comma) {
+            break;
+        }
+    }
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+/// function_declaration ::= function [ lifetime ] [ data_type_or_implicit ] identifier
+///     [ ( tf_port_list ) ] ; { block_item_declaration | statement_or_null }
+///     endfunction [ : identifier ]
+pub(crate) fn function_declaration(p: &mut Parser) {
+    subroutine_declaration(p, SyntaxKind::FunctionDeclaration, Token::EndFunction);
+}
+
+/// task_declaration ::= task [ lifetime ] identifier [ ( tf_port_list ) ] ;
+///     { block_item_declaration | statement_or_null } endtask [ : identifier ]
+pub(crate) fn task_declaration(p: &mut Parser) {
+    subroutine_declaration(p, SyntaxKind::TaskDeclaration, Token::EndTask);
+}
+
+fn subroutine_declaration(p: &mut Parser, kind: SyntaxKind, end: Token) {
+    p.start_node(kind);
+    p.bump();
+    if p.at_any(&[Token::Static, Token::Automatic]) {
+        p.bump();
+    }
+    // The return type is implicit when the name comes first
+    if !(p.at(Token::Identifier) && p.nth(1).is_some_and(|t| matches!(t, Token::LeftParen | Token::Semicolon))) {
+        data_type_or_implicit(p);
+    }
+    p.expect(Token::Identifier);
+    if p.at(Token::LeftParen) {
+        port_list(p);
+    }
+    p.expect(Token::Semicolon);
+    block_items(p, &[]);
+    p.expect(end);
+    end_label(p);
+    p.finish_node();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::test_util::parse_with;
+
+    #[test]
+    fn test_net_and_data_declarations() {
+        assert_eq!(
+            parse_with("wire signed [7:0] a, b = c;", module_item),
+            "(NetDeclaration wire (DataType signed (PackedDimension [ (Literal 7) : (Literal 0) ])) \
+             (Declarator a) , (Declarator b = (NameRef c)) ;)"
+        );
+        assert_eq!(
+            parse_with("const logic [1:0] mem [4];", module_item),
+            "(DataDeclaration const (DataType logic (PackedDimension [ (Literal 1) : (Literal 0) ])) \
+             (Declarator mem (UnpackedDimension [ (Literal 4) ])) ;)"
+        );
+        assert_eq!(
+            parse_with("pkg::word_t w;", module_item),
+            "(DataDeclaration (DataType pkg :: word_t) (Declarator w) ;)"
+        );
+    }
+
+    #[test]
+    fn test_net_strength_and_delay() {
+        assert_eq!(
+            parse_with("tri1 (strong0, weak1) vectored [3:0] #(1, 2) t = 4'h0;", module_item),
+            "(NetDeclaration tri1 (DriveStrength ( strong0 , weak1 )) vectored \
+             (DataType (PackedDimension [ (Literal 3) : (Literal 0) ])) (Delay3 # ( (Literal 1) , (Literal 2) )) \
+             (Declarator t = (Literal 4'h0)) ;)"
+        );
+        assert_eq!(
+            parse_with("trireg (small) #5ns c;", module_item),
+            "(NetDeclaration trireg (ChargeStrength ( small )) (Delay3 # (Literal 5) ns) (Declarator c) ;)"
+        );
+    }
+
+    #[test]
+    fn test_variable_dimensions() {
+        assert_eq!(
+            parse_with("logic [7:0] mem [0:255];", module_item),
+            "(DataDeclaration (DataType logic (PackedDimension [ (Literal 7) : (Literal 0) ])) \
+             (Declarator mem (UnpackedDimension [ (Literal 0) : (Literal 255) ])) ;)"
+        );
+        assert_eq!(
+            parse_with("int d [], a [*], s [string], q [$], b [$:15];", module_item),
+            "(DataDeclaration (DataType int) (Declarator d (UnpackedDimension [ ])) , \
+             (Declarator a (UnpackedDimension [ * ])) , (Declarator s (UnpackedDimension [ (DataType string) ])) , \
+             (Declarator q (UnpackedDimension [ (Literal $) ])) , \
+             (Declarator b (UnpackedDimension [ (Literal $) : (Literal 15) ])) ;)"
+        );
+    }
+
+    #[test]
+    fn test_typedefs() {
+        assert_eq!(
+            parse_with("typedef enum logic [1:0] {IDLE, RUN = 2, S[2:3]} state_t;", module_item),
+            "(TypedefDeclaration typedef (DataType (EnumType enum (DataType logic (PackedDimension [ (Literal 1) : (Literal 0) ])) \
+             { (EnumMember IDLE) , (EnumMember RUN = (Literal 2)) , (EnumMember S [ (Literal 2) : (Literal 3) ]) })) \
+             state_t ;)"
+        );
+        assert_eq!(
+            parse_with("typedef struct packed signed { logic [3:0] a; bit b, c; } pair_t [2];", module_item),
+            "(TypedefDeclaration typedef (DataType (StructUnionType struct packed signed { \
+             (StructMember (DataType logic (PackedDimension [ (Literal 3) : (Literal 0) ])) (Declarator a) ;) \
+             (StructMember (DataType bit) (Declarator b) , (Declarator c) ;) })) \
+             pair_t (UnpackedDimension [ (Literal 2) ]) ;)"
+        );
+        assert_eq!(
+            parse_with("typedef state_t; typedef union cell_t; union { int i; real r; } u;", |p| while !p.at_end() {
+                module_item(p)
+            }),
+            "(TypedefDeclaration typedef state_t ;) (TypedefDeclaration typedef union cell_t ;) \
+             (DataDeclaration (DataType (StructUnionType union { (StructMember (DataType int) (Declarator i) ;) \
+             (StructMember (DataType real) (Declarator r) ;) })) (Declarator u) ;)"
+        );
+    }
+
+    #[test]
+    fn test_parameter_declarations() {
+        assert_eq!(
+            parse_with("parameter W = 8, D = W * 2;", module_item),
+            "(ParameterDeclaration parameter (Declarator W = (Literal 8)) , \
+             (Declarator D = (BinaryExpression (NameRef W) * (Literal 2))) ;)"
+        );
+        assert_eq!(
+            parse_with("localparam int unsigned N = 4;", module_item),
+            "(ParameterDeclaration localparam (DataType int unsigned) (Declarator N = (Literal 4)) ;)"
+        );
+    }
+
+    #[test]
+    fn test_type_parameters() {
+        assert_eq!(
+            parse_with("parameter type T = logic [3:0], U;", module_item),
+            "(ParameterDeclaration parameter type (TypeAssignment T = (DataType logic \
+             (PackedDimension [ (Literal 3) : (Literal 0) ]))) , (TypeAssignment U) ;)"
+        );
+    }
+
+    #[test]
+    fn test_parameter_port_list() {
+        assert_eq!(
+            parse_with("#(parameter int DEPTH = 16, WIDTH = 8, type T = logic, localparam A = DEPTH - 1)", parameter_port_list),
+            "(ParameterPortList # ( (ParameterPortDeclaration parameter (DataType int) (Declarator DEPTH = (Literal 16))) , \
+             (ParameterPortDeclaration (Declarator WIDTH = (Literal 8))) , \
+             (ParameterPortDeclaration type (TypeAssignment T = (DataType logic))) , \
+             (ParameterPortDeclaration localparam (Declarator A = (BinaryExpression (NameRef DEPTH) - (Literal 1)))) ))"
+        );
+        assert_eq!(parse_with("#()", parameter_port_list), "(ParameterPortList # ( ))");
+    }
+
+    #[test]
+    fn test_subroutine_declarations() {
+        assert_eq!(
+            parse_with("function automatic logic f(input a); int i; return a; endfunction : f", module_item),
+            "(FunctionDeclaration function automatic (DataType logic) f (PortList ( (Port input a) )) ; \
+             (DataDeclaration (DataType int) (Declarator i) ;) (ReturnStatement return (NameRef a) ;) \
+             endfunction (EndLabel : f))"
+        );
+        assert_eq!(
+            parse_with("task t; endtask", module_item),
+            "(TaskDeclaration task t ; endtask)"
+        );
+    }
+}
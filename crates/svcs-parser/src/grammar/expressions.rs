@@ -0,0 +1,487 @@
+//! Expressions.
+
+use super::*;
+
+const UNARY_OPERATORS: &[Token] = &[
+    Token::Plus, Token::Minus, Token::LogicalNot, Token::BitwiseNot,
+    Token::BitwiseAnd, Token::BitwiseOr, Token::BitwiseXor,
+    Token::ReductionNand, Token::ReductionNor, Token::BitwiseXnor,
+];
+
+/// Binding power of a binary operator, IEEE 1800 table 11-2 from lowest to highest.
+/// All of them group left to right.
+fn binding_power(token: Token) -> Option<u8> {
+    let power = match token {
+        Token::LogicalOr => 1,
+        Token::LogicalAnd => 2,
+        Token::BitwiseOr => 3,
+        Token::BitwiseXor | Token::BitwiseXnor => 4,
+        Token::BitwiseAnd => 5,
+        Token::Equal | Token::NotEqual | Token::CaseEqual | Token::CaseNotEqual
+        | Token::WildcardEqual | Token::WildcardNotEqual => 6,
+        Token::LessThan | Token::LessEqual | Token::GreaterThan | Token::GreaterEqual | Token::Inside => 7,
+        Token::LeftShift | Token::RightShift | Token::ArithmeticLeftShift | Token::ArithmeticRightShift => 8,
+        Token::Plus | Token::Minus => 9,
+        Token::Multiply | Token::Divide | Token::Modulo => 10,
+        Token::Power => 11,
+        _ => return None,
+    };
+    Some(power)
+}
+
+/// expression ::= conditional_expression [ ( -> | <-> ) expression ]
+/// Implication and equivalence bind loosest and group right to left.
+pub(crate) fn expression(p: &mut Parser) {
+    let checkpoint = p.checkpoint();
+    conditional_expression(p);
+    if p.at_any(&[Token::Implication, Token::Equivalence]) {
+        p.start_node_at(checkpoint, SyntaxKind::BinaryExpression);
+        p.bump();
+        expression(p);
+        p.finish_node();
+    }
+}
+
+/// conditional_expression ::= binary_expression [ ? expression : conditional_expression ]
+fn conditional_expression(p: &mut Parser) {
+    let checkpoint = p.checkpoint();
+    binary_expression(p, 1);
+    if p.at(Token::Question) {
+        p.start_node_at(checkpoint, SyntaxKind::ConditionalExpression);
+        p.bump();
+        expression(p);
+        p.expect(Token::Colon);
+        conditional_expression(p);
+        p.finish_node();
+    }
+}
+
+/// Precedence climbing over the binary operators binding at least as tightly as
+/// `min_power`. `inside` sits among the relational operators but takes a range
+/// list as its right operand.
+fn binary_expression(p: &mut Parser, min_power: u8) {
+    let checkpoint = p.checkpoint();
+    unary_expression(p);
+    while let Some(power) = p.current().and_then(binding_power) {
+        if power < min_power {
+            break;
+        }
+        if p.at(Token::Inside) {
+            p.start_node_at(checkpoint, SyntaxKind::InsideExpression);
+            p.bump();
+            open_range_list(p);
+        } else {
+            p.start_node_at(checkpoint, SyntaxKind::BinaryExpression);
+            p.bump();
+            binary_expression(p, power + 1);
+        }
+        p.finish_node();
+    }
+}
+
+/// unary_operator unary_expression | inc_or_dec_operator lvalue | lvalue inc_or_dec_operator
+/// | postfix_expression
+fn unary_expression(p: &mut Parser) {
+    if p.at_any(UNARY_OPERATORS) {
+        p.start_node(SyntaxKind::UnaryExpression);
+        p.bump();
+        unary_expression(p);
+        p.finish_node();
+    } else if p.at_any(&[Token::Increment, Token::Decrement]) {
+        p.start_node(SyntaxKind::IncDecExpression);
+        p.bump();
+        postfix_expression(p);
+        p.finish_node();
+    } else {
+        let checkpoint = p.checkpoint();
+        postfix_expression(p);
+        if p.at_any(&[Token::Increment, Token::Decrement]) {
+            p.start_node_at(checkpoint, SyntaxKind::IncDecExpression);
+            p.bump();
+            p.finish_node();
+        }
+    }
+}
+
+/// A primary followed by selects, member accesses, call arguments, casts and
+/// typed assignment patterns; also used for lvalues
+pub(crate) fn postfix_expression(p: &mut Parser) {
+    let checkpoint = p.checkpoint();
+    primary(p);
+    loop {
+        match p.current() {
+            Some(Token::LeftBracket) => {
+                p.bump();
+                expression(p);
+                if p.at(Token::Colon) {
+                    p.start_node_at(checkpoint, SyntaxKind::RangeSelect);
+                    p.bump();
+                    expression(p);
+                } else if p.at_any(&[Token::IndexedPlus, Token::IndexedMinus]) {
+                    p.start_node_at(checkpoint, SyntaxKind::IndexedPartSelect);
+                    p.bump();
+                    expression(p);
+                } else {
+                    p.start_node_at(checkpoint, SyntaxKind::IndexExpression);
+                }
+                p.expect(Token::RightBracket);
+            }
+            Some(Token::Dot) => {
+                p.start_node_at(checkpoint, SyntaxKind::MemberAccess);
+                p.bump();
+                p.expect(Token::Identifier);
+            }
+            Some(Token::LeftParen) => {
+                p.start_node_at(checkpoint, SyntaxKind::CallExpression);
+                argument_list(p);
+            }
+            // `W'(x)`, `pkg::t'(x)`
+            Some(Token::Apostrophe) if p.nth_at(1, Token::LeftParen) => {
+                p.start_node_at(checkpoint, SyntaxKind::CastExpression);
+                cast_operand(p);
+            }
+            // `pair_t'{a, b}`
+            Some(Token::Apostrophe) if p.nth_at(1, Token::LeftBrace) => {
+                p.start_node_at(checkpoint, SyntaxKind::AssignmentPattern);
+                assignment_pattern_body(p);
+            }
+            _ => break,
+        }
+        p.finish_node();
+    }
+}
+
+/// list_of_arguments ::= ( [ expression { , expression } ] )
+fn argument_list(p: &mut Parser) {
+    p.start_node(SyntaxKind::ArgumentList);
+    p.bump();
+    if !p.at(Token::RightParen) {
+        loop {
+            expression(p);
+            if !p.eat(Token::Comma) {
+                break;
+            }
+        }
+    }
+    p.expect(Token::RightParen);
+    p.finish_node();
+}
+
+/// ' ( expression )
+fn cast_operand(p: &mut Parser) {
+    p.expect(Token::Apostrophe);
+    p.expect(Token::LeftParen);
+    expression(p);
+    p.expect(Token::RightParen);
+}
+
+/// primary ::= primary_literal | hierarchical_identifier | ( expression )
+///           | concatenation | multiple_concatenation | streaming_concatenation
+///           | cast | assignment_pattern_expression | type_reference | $
+pub(crate) fn primary(p: &mut Parser) {
+    match p.current() {
+        Some(
+            Token::SizedNumber | Token::UnsizedNumber | Token::RealNumber | Token::StringLiteral
+            | Token::UnbasedUnsizedLiteral | Token::Dollar,
+        ) => {
+            p.start_node(SyntaxKind::Literal);
+            p.bump();
+            p.finish_node();
+        }
+        Some(Token::Identifier | Token::SystemIdentifier) => name_ref(p),
+        Some(Token::LeftParen) => {
+            p.start_node(SyntaxKind::ParenExpression);
+            p.bump();
+            expression(p);
+            p.expect(Token::RightParen);
+            p.finish_node();
+        }
+        Some(Token::LeftBrace) => concatenation(p),
+        Some(Token::Apostrophe) if p.nth_at(1, Token::LeftBrace) => {
+            p.start_node(SyntaxKind::AssignmentPattern);
+            assignment_pattern_body(p);
+            p.finish_node();
+        }
+        // casting_type ' ( expression ) for built-in types and signing
+        Some(token)
+            if p.nth_at(1, Token::Apostrophe)
+                && (DATA_TYPE_KEYWORDS.contains(&token)
+                    || matches!(token, Token::Signed | Token::Unsigned | Token::Const)) =>
+        {
+            p.start_node(SyntaxKind::CastExpression);
+            p.bump();
+            cast_operand(p);
+            p.finish_node();
+        }
+        Some(Token::Type) if p.nth_at(1, Token::LeftParen) => type_reference(p),
+        _ => p.error_and_bump("an expression"),
+    }
+}
+
+/// type_reference ::= type ( expression ) | type ( data_type )
+/// Followed by `'(`, it is the casting type of a cast.
+fn type_reference(p: &mut Parser) {
+    p.start_node(SyntaxKind::TypeReference);
+    p.bump();
+    p.bump();
+    if p.at_any(DATA_TYPE_KEYWORDS) {
+        data_type(p);
+    } else {
+        expression(p);
+    }
+    p.expect(Token::RightParen);
+    p.finish_node();
+}
+
+/// [ package_scope ] identifier, or a system task/function name
+pub(crate) fn name_ref(p: &mut Parser) {
+    p.start_node(SyntaxKind::NameRef);
+    if !p.eat(Token::SystemIdentifier) {
+        p.expect(Token::Identifier);
+        while p.at(Token::Scope) && p.nth_at(1, Token::Identifier) {
+            p.bump();
+            p.bump();
+        }
+    }
+    p.finish_node();
+}
+
+/// concatenation ::= { expression { , expression } }
+/// multiple_concatenation ::= { expression concatenation }
+/// An empty `{}` is the empty queue.
+fn concatenation(p: &mut Parser) {
+    let checkpoint = p.checkpoint();
+    p.bump();
+    if p.at_any(&[Token::LeftShift, Token::RightShift]) {
+        p.start_node_at(checkpoint, SyntaxKind::StreamingConcatenation);
+        streaming_body(p);
+        p.finish_node();
+        return;
+    }
+    if p.at(Token::RightBrace) {
+        p.start_node_at(checkpoint, SyntaxKind::Concatenation);
+    } else {
+        expression(p);
+        if p.at(Token::LeftBrace) {
+            p.start_node_at(checkpoint, SyntaxKind::Replication);
+            concatenation(p);
+        } else {
+            p.start_node_at(checkpoint, SyntaxKind::Concatenation);
+            while p.eat(Token::Comma) {
+                expression(p);
+            }
+        }
+    }
+    p.expect(Token::RightBrace);
+    p.finish_node();
+}
+
+/// streaming_concatenation ::= { stream_operator [ slice_size ] stream_concatenation }
+/// with the opening brace already consumed
+fn streaming_body(p: &mut Parser) {
+    p.bump();
+    if !p.at(Token::LeftBrace) {
+        if p.at_any(DATA_TYPE_KEYWORDS) {
+            data_type(p);
+        } else {
+            expression(p);
+        }
+    }
+    if p.at(Token::LeftBrace) {
+        concatenation(p);
+    } else {
+        p.error("a stream concatenation");
+    }
+    p.expect(Token::RightBrace);
+}
+
+/// assignment_pattern ::= '{ expression { , expression } }
+///                      | '{ structure_pattern_key : expression { , ... } }
+///                      | '{ constant_expression { expression { , expression } } }
+fn assignment_pattern_body(p: &mut Parser) {
+    p.expect(Token::Apostrophe);
+    p.expect(Token::LeftBrace);
+    if !p.at(Token::RightBrace) {
+        pattern_item(p);
+        if p.at(Token::LeftBrace) {
+            // Replication: the count applies to the inner list
+            concatenation(p);
+        } else {
+            while p.eat(Token::Comma) {
+                pattern_item(p);
+            }
+        }
+    }
+    p.expect(Token::RightBrace);
+}
+
+/// expression, or key : expression where the key is a member, type, index or `default`
+fn pattern_item(p: &mut Parser) {
+    let checkpoint = p.checkpoint();
+    let default = p.eat(Token::Default);
+    if !default {
+        expression(p);
+    }
+    if default || p.at(Token::Colon) {
+        p.start_node_at(checkpoint, SyntaxKind::PatternKeyValue);
+        p.expect(Token::Colon);
+        expression(p);
+        p.finish_node();
+    }
+}
+
+/// open_range_list ::= { value_range { , value_range } }
+/// value_range ::= expression | [ expression : expression ]
+pub(crate) fn open_range_list(p: &mut Parser) {
+    p.expect(Token::LeftBrace);
+    loop {
+        open_value_range(p);
+        if !p.eat(Token::Comma) {
+            break;
+        }
+    }
+    p.expect(Token::RightBrace);
+}
+
+/// open_value_range ::= value_range | expression
+pub(crate) fn open_value_range(p: &mut Parser) {
+    if p.at(Token::LeftBracket) {
+        p.start_node(SyntaxKind::ValueRange);
+        p.bump();
+        expression(p);
+        p.expect(Token::Colon);
+        expression(p);
+        p.expect(Token::RightBracket);
+        p.finish_node();
+    } else {
+        expression(p);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::test_util::parse_with;
+
+    #[test]
+    fn test_operators() {
+        assert_eq!(
+            parse_with("carry1 | carry2 & c", expression),
+            "(BinaryExpression (NameRef carry1) | (BinaryExpression (NameRef carry2) & (NameRef c)))"
+        );
+        assert_eq!(
+            parse_with("a - b - c * d ** 2", expression),
+            "(BinaryExpression (BinaryExpression (NameRef a) - (NameRef b)) - \
+             (BinaryExpression (NameRef c) * (BinaryExpression (NameRef d) ** (Literal 2))))"
+        );
+        assert_eq!(
+            parse_with("a << 1 < b == c && d || e", expression),
+            "(BinaryExpression (BinaryExpression (BinaryExpression (BinaryExpression \
+             (BinaryExpression (NameRef a) << (Literal 1)) < (NameRef b)) == (NameRef c)) && (NameRef d)) || (NameRef e))"
+        );
+        assert_eq!(
+            parse_with("!a ? -1 : ~&b", expression),
+            "(ConditionalExpression (UnaryExpression ! (NameRef a)) ? (UnaryExpression - (Literal 1)) : \
+             (UnaryExpression ~& (NameRef b)))"
+        );
+        // The conditional operator groups right to left
+        assert_eq!(
+            parse_with("a ? b : c ? d : e", expression),
+            "(ConditionalExpression (NameRef a) ? (NameRef b) : \
+             (ConditionalExpression (NameRef c) ? (NameRef d) : (NameRef e)))"
+        );
+        assert_eq!(
+            parse_with("a -> b <-> c", expression),
+            "(BinaryExpression (NameRef a) -> (BinaryExpression (NameRef b) <-> (NameRef c)))"
+        );
+        assert_eq!(
+            parse_with("i++ + --j", expression),
+            "(BinaryExpression (IncDecExpression (NameRef i) ++) + (IncDecExpression -- (NameRef j)))"
+        );
+    }
+
+    #[test]
+    fn test_inside() {
+        assert_eq!(
+            parse_with("x + 1 inside {1, [4:$]} && y", expression),
+            "(BinaryExpression (InsideExpression (BinaryExpression (NameRef x) + (Literal 1)) inside { \
+             (Literal 1) , (ValueRange [ (Literal 4) : (Literal $) ]) }) && (NameRef y))"
+        );
+    }
+
+    #[test]
+    fn test_selects_and_members() {
+        assert_eq!(
+            parse_with("s.data[i+:8]", expression),
+            "(IndexedPartSelect (MemberAccess (NameRef s) . data) [ (NameRef i) +: (Literal 8) ])"
+        );
+        assert_eq!(
+            parse_with("q.size() - 1", expression),
+            "(BinaryExpression (CallExpression (MemberAccess (NameRef q) . size) (ArgumentList ( ))) - (Literal 1))"
+        );
+        assert_eq!(
+            parse_with("m[hi-:4]", expression),
+            "(IndexedPartSelect (NameRef m) [ (NameRef hi) -: (Literal 4) ])"
+        );
+    }
+
+    #[test]
+    fn test_casts() {
+        assert_eq!(
+            parse_with("int'(x) + signed'(y)", expression),
+            "(BinaryExpression (CastExpression int ' ( (NameRef x) )) + (CastExpression signed ' ( (NameRef y) )))"
+        );
+        assert_eq!(
+            parse_with("W'(a) == pkg::t'(b)", expression),
+            "(BinaryExpression (CastExpression (NameRef W) ' ( (NameRef a) )) == \
+             (CastExpression (NameRef pkg :: t) ' ( (NameRef b) )))"
+        );
+        assert_eq!(
+            parse_with("type(k)'(3) + type(logic [3:0])'(x)", expression),
+            "(BinaryExpression (CastExpression (TypeReference type ( (NameRef k) )) ' ( (Literal 3) )) + \
+             (CastExpression (TypeReference type ( (DataType logic (PackedDimension [ (Literal 3) : (Literal 0) ])) )) \
+             ' ( (NameRef x) )))"
+        );
+    }
+
+    #[test]
+    fn test_streaming_and_patterns() {
+        assert_eq!(
+            parse_with("{<< byte {a, b}}", expression),
+            "(StreamingConcatenation { << (DataType byte) (Concatenation { (NameRef a) , (NameRef b) }) })"
+        );
+        assert_eq!(
+            parse_with("{>> 4 {x}}", expression),
+            "(StreamingConcatenation { >> (Literal 4) (Concatenation { (NameRef x) }) })"
+        );
+        assert_eq!(
+            parse_with("'{a: 1, default: '0}", expression),
+            "(AssignmentPattern ' { (PatternKeyValue (NameRef a) : (Literal 1)) , \
+             (PatternKeyValue default : (Literal '0)) })"
+        );
+        assert_eq!(
+            parse_with("pair_t'{2{x}}", expression),
+            "(AssignmentPattern (NameRef pair_t) ' { (Literal 2) (Concatenation { (NameRef x) }) })"
+        );
+        assert_eq!(parse_with("{}", expression), "(Concatenation { })");
+    }
+
+    #[test]
+    fn test_primaries() {
+        assert_eq!(
+            parse_with("{a, 4'b0}", expression),
+            "(Concatenation { (NameRef a) , (Literal 4'b0) })"
+        );
+        assert_eq!(
+            parse_with("{2{x}}", expression),
+            "(Replication { (Literal 2) (Concatenation { (NameRef x) }) })"
+        );
+        assert_eq!(
+            parse_with("pkg::f(m[3][7:0], \"s\", (y))", expression),
+            "(CallExpression (NameRef pkg :: f) (ArgumentList ( \
+             (RangeSelect (IndexExpression (NameRef m) [ (Literal 3) ]) [ (Literal 7) : (Literal 0) ]) , \
+             (Literal \"s\") , (ParenExpression ( (NameRef y) )) )))"
+        );
+        assert_eq!(parse_with("$time", expression), "(NameRef $time)");
+    }
+}
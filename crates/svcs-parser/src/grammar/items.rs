@@ -0,0 +1,733 @@
+//! Design units and the items they contain.
+
+use super::*;
+
+/// Keywords starting a package item, where recovery from a bad item stops
+const PACKAGE_ITEM_START: &[Token] = &[
+    Token::Parameter, Token::LocalParam, Token::Function, Token::Task, Token::Import,
+    Token::Typedef,
+];
+
+/// Keywords starting a module item, where recovery from a bad item stops
+const MODULE_ITEM_START: &[Token] = &[
+    Token::AssignKeyword, Token::Always, Token::AlwaysComb, Token::AlwaysFF, Token::AlwaysLatch,
+    Token::Initial, Token::Final, Token::Generate, Token::Input, Token::Output, Token::Inout,
+    Token::Ref, Token::GenVar, Token::Parameter, Token::LocalParam, Token::Function,
+    Token::Task, Token::Import, Token::Typedef, Token::ModPort, Token::For, Token::If, Token::Case,
+];
+
+/// port_direction keywords
+pub(crate) const PORT_DIRECTIONS: &[Token] = &[Token::Input, Token::Output, Token::Inout, Token::Ref];
+
+/// module_declaration, interface_declaration and program_declaration share one shape:
+/// keyword [ lifetime ] identifier { package_import_declaration } [ parameter_port_list ]
+///     [ list_of_ports ] ; { item } end_keyword [ : identifier ]
+pub(crate) fn design_unit(p: &mut Parser, kind: SyntaxKind, end: Token) {
+    p.start_node(kind);
+    p.bump();
+    if p.at_any(&[Token::Static, Token::Automatic]) {
+        p.bump();
+    }
+    p.expect(Token::Identifier);
+    while p.at(Token::Import) {
+        package_import_declaration(p);
+    }
+    if p.at(Token::Hash) {
+        parameter_port_list(p);
+    }
+    if p.at(Token::LeftParen) {
+        port_list(p);
+    }
+    p.expect(Token::Semicolon);
+    while !p.at_end() && !p.at(end.clone()) {
+        module_item(p);
+    }
+    p.expect(end);
+    end_label(p);
+    p.finish_node();
+}
+
+/// package_declaration ::= package [ lifetime ] identifier ; { package_item } endpackage [ : identifier ]
+pub(crate) fn package_declaration(p: &mut Parser) {
+    p.start_node(SyntaxKind::PackageDeclaration);
+    p.bump();
+    if p.at_any(&[Token::Static, Token::Automatic]) {
+        p.bump();
+    }
+    p.expect(Token::Identifier);
+    p.expect(Token::Semicolon);
+    while !p.at_end() && !p.at(Token::EndPackage) {
+        if at_package_item(p) {
+            package_item(p);
+        } else {
+            p.error_and_recover("a package item", PACKAGE_ITEM_START);
+        }
+    }
+    p.expect(Token::EndPackage);
+    end_label(p);
+    p.finish_node();
+}
+
+pub(crate) fn at_package_item(p: &Parser) -> bool {
+    p.at_any(&[Token::Parameter, Token::LocalParam, Token::Function, Token::Task, Token::Import, Token::Typedef])
+        || p.at_any(NET_TYPE_KEYWORDS)
+        || at_data_declaration(p)
+}
+
+/// package_item ::= net_declaration | data_declaration | type_declaration | parameter_declaration
+///                | function_declaration | task_declaration | package_import_declaration
+pub(crate) fn package_item(p: &mut Parser) {
+    match p.current() {
+        Some(Token::Parameter | Token::LocalParam) => parameter_declaration(p),
+        Some(Token::Typedef) => type_declaration(p),
+        Some(Token::Function) => function_declaration(p),
+        Some(Token::Task) => task_declaration(p),
+        Some(Token::Import) => package_import_declaration(p),
+        _ if p.at_any(NET_TYPE_KEYWORDS) => net_declaration(p),
+        _ => data_declaration(p),
+    }
+}
+
+/// list_of_ports ::= ( [ port { , port } ] )
+pub(crate) fn port_list(p: &mut Parser) {
+    p.start_node(SyntaxKind::PortList);
+    p.expect(Token::LeftParen);
+    if !p.at(Token::RightParen) {
+        loop {
+            port(p);
+            if !p.eat(Token::Comma) {
+                break;
+            }
+        }
+    }
+    p.expect(Token::RightParen);
+    p.finish_node();
+}
+
+/// ansi_port_declaration ::= [ port_direction ] [ net_type | var ] data_type_or_implicit
+///                               identifier { unpacked_dimension } [ = constant_expression ]
+///                           | [ port_direction ] interface_port_header identifier { unpacked_dimension }
+///                           | [ port_direction ] . port_identifier ( [ expression ] )
+/// A bare identifier is a non-ANSI port name, or an ANSI port inheriting the
+/// direction and type of the previous one.
+pub(crate) fn port(p: &mut Parser) {
+    p.start_node(SyntaxKind::Port);
+    if p.at_any(PORT_DIRECTIONS) {
+        p.bump();
+    }
+    if p.at(Token::Dot) {
+        p.bump();
+        p.expect(Token::Identifier);
+        p.expect(Token::LeftParen);
+        if !p.at(Token::RightParen) {
+            expression(p);
+        }
+        p.expect(Token::RightParen);
+        p.finish_node();
+        return;
+    }
+    if at_interface_port_header(p) {
+        interface_port_header(p);
+    } else {
+        port_kind_and_type(p);
+    }
+    p.expect(Token::Identifier);
+    while p.at(Token::LeftBracket) {
+        unpacked_dimension(p);
+    }
+    if p.eat(Token::Assign) {
+        expression(p);
+    }
+    p.finish_node();
+}
+
+/// [ net_type | var ] data_type_or_implicit
+fn port_kind_and_type(p: &mut Parser) {
+    if p.at_any(NET_TYPE_KEYWORDS) || p.at(Token::Var) {
+        p.bump();
+    }
+    data_type_or_implicit(p);
+}
+
+/// `interface [. modport]`, or `bus_if . modport` followed by the port name.
+/// Without a modport an interface port looks like a port of a user type.
+fn at_interface_port_header(p: &Parser) -> bool {
+    p.at(Token::Interface)
+        || (p.at(Token::Identifier)
+            && p.nth_at(1, Token::Dot)
+            && p.nth_at(2, Token::Identifier)
+            && p.nth_at(3, Token::Identifier))
+}
+
+/// interface_port_header ::= interface_identifier [ . modport_identifier ]
+///                         | interface [ . modport_identifier ]
+fn interface_port_header(p: &mut Parser) {
+    p.start_node(SyntaxKind::InterfacePortHeader);
+    p.bump();
+    if p.eat(Token::Dot) {
+        p.expect(Token::Identifier);
+    }
+    p.finish_node();
+}
+
+/// module_item ::= port_declaration ; | module_or_generate_item | generate_region
+///               | non_port_module_item
+pub(crate) fn module_item(p: &mut Parser) {
+    match p.current() {
+        Some(Token::Semicolon) => {
+            p.start_node(SyntaxKind::EmptyItem);
+            p.bump();
+            p.finish_node();
+        }
+        Some(Token::PreprocessorDirective) => directive(p),
+        // Opaque encrypted contents; the enclosing module becomes a black box
+        Some(Token::ProtectedEnvelope) => p.bump(),
+        Some(Token::AssignKeyword) => continuous_assign(p),
+        Some(Token::Always | Token::AlwaysComb | Token::AlwaysFF | Token::AlwaysLatch) => {
+            procedural_construct(p, SyntaxKind::AlwaysConstruct)
+        }
+        Some(Token::Initial) => procedural_construct(p, SyntaxKind::InitialConstruct),
+        Some(Token::Final) => procedural_construct(p, SyntaxKind::FinalConstruct),
+        Some(Token::Generate) => generate_region(p),
+        Some(Token::For) => loop_generate_construct(p),
+        Some(Token::If) => if_generate_construct(p),
+        Some(Token::Case) => case_generate_construct(p),
+        Some(Token::ModPort) => modport_declaration(p),
+        Some(Token::Input | Token::Output | Token::Inout | Token::Ref) => port_declaration(p),
+        Some(Token::GenVar) => genvar_declaration(p),
+        Some(Token::Identifier) if at_module_instantiation(p) => module_instantiation(p),
+        _ if at_package_item(p) => package_item(p),
+        _ => p.error_and_recover("a module item", MODULE_ITEM_START),
+    }
+}
+
+/// port_declaration ::= port_direction [ net_type | var ] data_type_or_implicit
+///                      list_of_port_identifiers ;
+///                    | port_direction interface_port_header list_of_port_identifiers ;
+pub(crate) fn port_declaration(p: &mut Parser) {
+    p.start_node(SyntaxKind::PortDeclaration);
+    p.bump();
+    if at_interface_port_header(p) {
+        interface_port_header(p);
+    } else {
+        port_kind_and_type(p);
+    }
+    list_of_declarators(p);
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+/// continuous_assign ::= assign [ drive_strength ] [ delay3 ] net_assignment { , net_assignment } ;
+pub(crate) fn continuous_assign(p: &mut Parser) {
+    p.start_node(SyntaxKind::ContinuousAssign);
+    p.bump();
+    if p.at(Token::LeftParen) {
+        strength(p);
+    }
+    if p.at(Token::Hash) {
+        delay3(p);
+    }
+    loop {
+        net_assignment(p);
+        if !p.eat(Token::Comma) {
+            break;
+        }
+    }
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+/// net_assignment ::= net_lvalue = expression
+fn net_assignment(p: &mut Parser) {
+    p.start_node(SyntaxKind::NetAssignment);
+    postfix_expression(p);
+    p.expect(Token::Assign);
+    expression(p);
+    p.finish_node();
+}
+
+/// always_construct, initial_construct and final_construct: keyword statement
+fn procedural_construct(p: &mut Parser, kind: SyntaxKind) {
+    p.start_node(kind);
+    p.bump();
+    statement(p);
+    p.finish_node();
+}
+
+/// generate_region ::= generate { module_item } endgenerate
+pub(crate) fn generate_region(p: &mut Parser) {
+    p.start_node(SyntaxKind::GenerateRegion);
+    p.bump();
+    while !p.at_end() && !p.at_unit_end() {
+        module_item(p);
+    }
+    p.expect(Token::EndGenerate);
+    p.finish_node();
+}
+
+/// loop_generate_construct ::= for ( genvar_initialization ; genvar_expression ; genvar_iteration )
+///                             generate_block
+fn loop_generate_construct(p: &mut Parser) {
+    p.start_node(SyntaxKind::LoopGenerateConstruct);
+    p.bump();
+    p.expect(Token::LeftParen);
+    genvar_initialization(p);
+    p.expect(Token::Semicolon);
+    expression(p);
+    p.expect(Token::Semicolon);
+    variable_assignment(p);
+    p.expect(Token::RightParen);
+    generate_block(p);
+    p.finish_node();
+}
+
+/// genvar_initialization ::= [ genvar ] genvar_identifier = constant_expression
+fn genvar_initialization(p: &mut Parser) {
+    p.start_node(SyntaxKind::GenvarInitialization);
+    p.eat(Token::GenVar);
+    p.expect(Token::Identifier);
+    p.expect(Token::Assign);
+    expression(p);
+    p.finish_node();
+}
+
+/// if_generate_construct ::= if ( constant_expression ) generate_block [ else generate_block ]
+fn if_generate_construct(p: &mut Parser) {
+    p.start_node(SyntaxKind::IfGenerateConstruct);
+    p.bump();
+    p.expect(Token::LeftParen);
+    expression(p);
+    p.expect(Token::RightParen);
+    generate_block(p);
+    if p.eat(Token::Else) {
+        generate_block(p);
+    }
+    p.finish_node();
+}
+
+/// case_generate_construct ::= case ( constant_expression ) case_generate_item
+///                             { case_generate_item } endcase
+fn case_generate_construct(p: &mut Parser) {
+    p.start_node(SyntaxKind::CaseGenerateConstruct);
+    p.bump();
+    p.expect(Token::LeftParen);
+    expression(p);
+    p.expect(Token::RightParen);
+    while !p.at_end() && !p.at(Token::EndCase) && !p.at_unit_end() {
+        if p.at_block_end() {
+            p.error_and_recover("a case item", MODULE_ITEM_START);
+        } else {
+            case_generate_item(p);
+        }
+    }
+    p.expect(Token::EndCase);
+    p.finish_node();
+}
+
+/// case_generate_item ::= constant_expression { , constant_expression } : generate_block
+///                      | default [ : ] generate_block
+fn case_generate_item(p: &mut Parser) {
+    p.start_node(SyntaxKind::CaseGenerateItem);
+    if p.eat(Token::Default) {
+        p.eat(Token::Colon);
+    } else {
+        loop {
+            expression(p);
+            if !p.eat(Token::Comma) {
+                break;
+            }
+        }
+        p.expect(Token::Colon);
+    }
+    generate_block(p);
+    p.finish_node();
+}
+
+/// generate_block ::= generate_item
+///                  | [ generate_block_identifier : ] begin [ : generate_block_identifier ]
+///                        { generate_item } end [ : generate_block_identifier ]
+fn generate_block(p: &mut Parser) {
+    let labeled = p.at(Token::Identifier) && p.nth_at(1, Token::Colon) && p.nth_at(2, Token::Begin);
+    if !labeled && !p.at(Token::Begin) {
+        module_item(p);
+        return;
+    }
+    p.start_node(SyntaxKind::GenerateBlock);
+    if labeled {
+        p.bump();
+        p.bump();
+    }
+    p.bump();
+    end_label(p);
+    while !p.at_end() && !p.at(Token::End) && !p.at_unit_end() {
+        module_item(p);
+    }
+    p.expect(Token::End);
+    end_label(p);
+    p.finish_node();
+}
+
+/// modport_declaration ::= modport modport_item { , modport_item } ;
+fn modport_declaration(p: &mut Parser) {
+    p.start_node(SyntaxKind::ModportDeclaration);
+    p.bump();
+    loop {
+        modport_item(p);
+        if !p.eat(Token::Comma) {
+            break;
+        }
+    }
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+/// modport_item ::= modport_identifier ( modport_ports_declaration { , modport_ports_declaration } )
+fn modport_item(p: &mut Parser) {
+    p.start_node(SyntaxKind::ModportItem);
+    p.expect(Token::Identifier);
+    p.expect(Token::LeftParen);
+    loop {
+        modport_ports_declaration(p);
+        if !p.eat(Token::Comma) {
+            break;
+        }
+    }
+    p.expect(Token::RightParen);
+    p.finish_node();
+}
+
+/// modport_ports_declaration ::= port_direction modport_simple_port { , modport_simple_port }
+///                             | import_export modport_tf_port { , modport_tf_port }
+///                             | clocking clocking_identifier
+/// Subroutine ports are names only; prototypes are not supported.
+fn modport_ports_declaration(p: &mut Parser) {
+    p.start_node(SyntaxKind::ModportPortsDeclaration);
+    if p.at_any(PORT_DIRECTIONS) || p.at_any(&[Token::Import, Token::Export, Token::Clocking]) {
+        p.bump();
+    } else {
+        p.error("a port direction, import, export or clocking");
+    }
+    loop {
+        modport_port(p);
+        // A comma before a keyword starts the next declaration
+        if !p.at(Token::Comma) || !p.nth_at(1, Token::Identifier) && !p.nth_at(1, Token::Dot) {
+            break;
+        }
+        p.bump();
+    }
+    p.finish_node();
+}
+
+/// modport_simple_port ::= port_identifier | . port_identifier ( [ expression ] )
+fn modport_port(p: &mut Parser) {
+    p.start_node(SyntaxKind::ModportPort);
+    if p.eat(Token::Dot) {
+        p.expect(Token::Identifier);
+        p.expect(Token::LeftParen);
+        if !p.at(Token::RightParen) {
+            expression(p);
+        }
+        p.expect(Token::RightParen);
+    } else {
+        p.expect(Token::Identifier);
+    }
+    p.finish_node();
+}
+
+/// `type_name [ #(...) ] instance_name (` where the instance name may carry
+/// dimensions. Anything else starting with two identifiers is a declaration of a
+/// user type.
+fn at_module_instantiation(p: &Parser) -> bool {
+    let mut n = 1;
+    if p.nth_at(1, Token::Hash) && p.nth_at(2, Token::LeftParen) {
+        let mut depth = 0;
+        n = 2;
+        while let Some(token) = p.nth(n) {
+            n += 1;
+            match token {
+                Token::LeftParen => depth += 1,
+                Token::RightParen if depth == 1 => break,
+                Token::RightParen => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+    if !p.nth_at(n, Token::Identifier) {
+        return false;
+    }
+    n += 1;
+    let mut depth = 0;
+    while let Some(token) = p.nth(n) {
+        match token {
+            Token::LeftBracket => depth += 1,
+            Token::RightBracket => depth -= 1,
+            Token::LeftParen if depth == 0 => return true,
+            _ if depth == 0 => return false,
+            _ => {}
+        }
+        n += 1;
+    }
+    false
+}
+
+/// module_instantiation ::= module_identifier [ parameter_value_assignment ]
+///                          hierarchical_instance { , hierarchical_instance } ;
+pub(crate) fn module_instantiation(p: &mut Parser) {
+    p.start_node(SyntaxKind::ModuleInstantiation);
+    p.bump();
+    if p.at(Token::Hash) {
+        parameter_value_assignment(p);
+    }
+    loop {
+        hierarchical_instance(p);
+        if !p.eat(Token::Comma) {
+            break;
+        }
+    }
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+/// parameter_value_assignment ::= # ( [ list_of_parameter_assignments ] )
+fn parameter_value_assignment(p: &mut Parser) {
+    p.start_node(SyntaxKind::ParameterValueAssignment);
+    p.bump();
+    p.expect(Token::LeftParen);
+    if !p.at(Token::RightParen) {
+        loop {
+            parameter_assignment(p);
+            if !p.eat(Token::Comma) {
+                break;
+            }
+        }
+    }
+    p.expect(Token::RightParen);
+    p.finish_node();
+}
+
+/// named_parameter_assignment ::= . parameter_identifier ( [ param_expression ] )
+/// ordered_parameter_assignment ::= param_expression
+fn parameter_assignment(p: &mut Parser) {
+    if p.at(Token::Dot) {
+        p.start_node(SyntaxKind::NamedParameterAssignment);
+        p.bump();
+        p.expect(Token::Identifier);
+        p.expect(Token::LeftParen);
+        if !p.at(Token::RightParen) {
+            param_expression(p);
+        }
+        p.expect(Token::RightParen);
+    } else {
+        p.start_node(SyntaxKind::OrderedParameterAssignment);
+        param_expression(p);
+    }
+    p.finish_node();
+}
+
+/// param_expression ::= expression | data_type
+/// A user type name parses as a name reference.
+fn param_expression(p: &mut Parser) {
+    if p.at_any(DATA_TYPE_KEYWORDS) {
+        data_type(p);
+    } else {
+        expression(p);
+    }
+}
+
+/// hierarchical_instance ::= instance_identifier { unpacked_dimension } ( [ list_of_port_connections ] )
+fn hierarchical_instance(p: &mut Parser) {
+    p.start_node(SyntaxKind::HierarchicalInstance);
+    p.expect(Token::Identifier);
+    while p.at(Token::LeftBracket) {
+        unpacked_dimension(p);
+    }
+    p.start_node(SyntaxKind::PortConnectionList);
+    p.expect(Token::LeftParen);
+    if !p.at(Token::RightParen) {
+        loop {
+            port_connection(p);
+            if !p.eat(Token::Comma) {
+                break;
+            }
+        }
+    }
+    p.expect(Token::RightParen);
+    p.finish_node();
+    p.finish_node();
+}
+
+/// named_port_connection ::= . port_identifier [ ( [ expression ] ) ] | .*
+/// ordered_port_connection ::= [ expression ]
+fn port_connection(p: &mut Parser) {
+    if p.at(Token::Dot) && p.nth_at(1, Token::Multiply) {
+        p.start_node(SyntaxKind::WildcardPortConnection);
+        p.bump();
+        p.bump();
+    } else if p.at(Token::Dot) && !p.nth_at(2, Token::LeftParen) {
+        p.start_node(SyntaxKind::ImplicitPortConnection);
+        p.bump();
+        p.expect(Token::Identifier);
+    } else if p.at(Token::Dot) {
+        p.start_node(SyntaxKind::NamedPortConnection);
+        p.bump();
+        p.expect(Token::Identifier);
+        p.expect(Token::LeftParen);
+        if !p.at(Token::RightParen) {
+            expression(p);
+        }
+        p.expect(Token::RightParen);
+    } else {
+        // A blank between commas leaves the port unconnected
+        p.start_node(SyntaxKind::OrderedPortConnection);
+        if !p.at_any(&[Token::Comma, Token::RightParen]) {
+            expression(p);
+        }
+    }
+    p.finish_node();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::test_util::parse_with;
+
+    #[test]
+    fn test_module_header_and_ports() {
+        assert_eq!(
+            parse_with("module automatic m(input wire [3:0] a, output logic b, c); endmodule", source_text),
+            "(SourceFile (ModuleDeclaration module automatic m (PortList ( \
+             (Port input wire (DataType (PackedDimension [ (Literal 3) : (Literal 0) ])) a) , \
+             (Port output (DataType logic) b) , (Port c) )) ; endmodule))"
+        );
+    }
+
+    #[test]
+    fn test_parameterized_module_header() {
+        assert_eq!(
+            parse_with("module fifo #(parameter int DEPTH = 16, type T = logic) (input T d); endmodule", source_text),
+            "(SourceFile (ModuleDeclaration module fifo (ParameterPortList # ( \
+             (ParameterPortDeclaration parameter (DataType int) (Declarator DEPTH = (Literal 16))) , \
+             (ParameterPortDeclaration type (TypeAssignment T = (DataType logic))) )) \
+             (PortList ( (Port input (DataType T) d) )) ; endmodule))"
+        );
+    }
+
+    #[test]
+    fn test_package_imports_in_module_header() {
+        assert_eq!(
+            parse_with("module m import pkg::*; import cfg::W; #(int N = W) (input logic clk); endmodule", source_text),
+            "(SourceFile (ModuleDeclaration module m \
+             (PackageImportDeclaration import (PackageImportItem pkg :: *) ;) \
+             (PackageImportDeclaration import (PackageImportItem cfg :: W) ;) \
+             (ParameterPortList # ( (ParameterPortDeclaration (DataType int) (Declarator N = (NameRef W))) )) \
+             (PortList ( (Port input (DataType logic) clk) )) ; endmodule))"
+        );
+    }
+
+    #[test]
+    fn test_port_styles() {
+        assert_eq!(
+            parse_with("(output reg signed [3:0] q, bus_if.master m, interface s, ref var int r, input int d = 4)", port_list),
+            "(PortList ( (Port output (DataType reg signed (PackedDimension [ (Literal 3) : (Literal 0) ])) q) , \
+             (Port (InterfacePortHeader bus_if . master) m) , (Port (InterfacePortHeader interface) s) , \
+             (Port ref var (DataType int) r) , (Port input (DataType int) d = (Literal 4)) ))"
+        );
+        assert_eq!(
+            parse_with("(a, .b(c[1]), .d())", port_list),
+            "(PortList ( (Port a) , (Port . b ( (IndexExpression (NameRef c) [ (Literal 1) ]) )) , (Port . d ( )) ))"
+        );
+        assert_eq!(
+            parse_with("input tri1 [1:0] a, b; ref logic r;", |p| while !p.at_end() { module_item(p) }),
+            "(PortDeclaration input tri1 (DataType (PackedDimension [ (Literal 1) : (Literal 0) ])) \
+             (Declarator a) , (Declarator b) ;) (PortDeclaration ref (DataType logic) (Declarator r) ;)"
+        );
+    }
+
+    #[test]
+    fn test_module_items() {
+        assert_eq!(
+            parse_with("input a, b; wire w; genvar i; initial x = 1; final y = 2;", |p| while !p.at_end() { module_item(p) }),
+            "(PortDeclaration input (Declarator a) , (Declarator b) ;) \
+             (NetDeclaration wire (Declarator w) ;) (GenvarDeclaration genvar i ;) \
+             (InitialConstruct initial (BlockingAssignment (NameRef x) = (Literal 1) ;)) \
+             (FinalConstruct final (BlockingAssignment (NameRef y) = (Literal 2) ;))"
+        );
+        assert_eq!(
+            parse_with("generate ; endgenerate", module_item),
+            "(GenerateRegion generate (EmptyItem ;) endgenerate)"
+        );
+    }
+
+    #[test]
+    fn test_continuous_assign() {
+        assert_eq!(
+            parse_with("assign a = b, c[1] = d;", module_item),
+            "(ContinuousAssign assign (NetAssignment (NameRef a) = (NameRef b)) , \
+             (NetAssignment (IndexExpression (NameRef c) [ (Literal 1) ]) = (NameRef d)) ;)"
+        );
+        assert_eq!(
+            parse_with("assign #1 io = en ? d : 'z;", module_item),
+            "(ContinuousAssign assign (Delay3 # (Literal 1)) (NetAssignment (NameRef io) = \
+             (ConditionalExpression (NameRef en) ? (NameRef d) : (Literal 'z))) ;)"
+        );
+        assert_eq!(
+            parse_with("assign (strong0, weak1) #(2, 3) y = a;", module_item),
+            "(ContinuousAssign assign (DriveStrength ( strong0 , weak1 )) \
+             (Delay3 # ( (Literal 2) , (Literal 3) )) (NetAssignment (NameRef y) = (NameRef a)) ;)"
+        );
+    }
+
+    #[test]
+    fn test_port_connection_styles() {
+        assert_eq!(
+            parse_with("m u1(.a(x[3:0] + 1), .b, .*), u2(y, , z);", module_item),
+            "(ModuleInstantiation m (HierarchicalInstance u1 (PortConnectionList ( \
+             (NamedPortConnection . a ( (BinaryExpression (RangeSelect (NameRef x) [ (Literal 3) : (Literal 0) ]) + (Literal 1)) )) , \
+             (ImplicitPortConnection . b) , (WildcardPortConnection . *) ))) , \
+             (HierarchicalInstance u2 (PortConnectionList ( (OrderedPortConnection (NameRef y)) , \
+             (OrderedPortConnection) , (OrderedPortConnection (NameRef z)) ))) ;)"
+        );
+    }
+
+    #[test]
+    fn test_instance_array() {
+        assert_eq!(
+            parse_with("dff u[3:0] (.*);", module_item),
+            "(ModuleInstantiation dff (HierarchicalInstance u (UnpackedDimension [ (Literal 3) : (Literal 0) ]) \
+             (PortConnectionList ( (WildcardPortConnection . *) ))) ;)"
+        );
+    }
+
+    #[test]
+    fn test_parameter_value_assignment() {
+        assert_eq!(
+            parse_with("fifo #(.DEPTH(32), .T(logic [7:0]), .W()) u_fifo (.d(x));", module_item),
+            "(ModuleInstantiation fifo (ParameterValueAssignment # ( \
+             (NamedParameterAssignment . DEPTH ( (Literal 32) )) , \
+             (NamedParameterAssignment . T ( (DataType logic (PackedDimension [ (Literal 7) : (Literal 0) ])) )) , \
+             (NamedParameterAssignment . W ( )) )) \
+             (HierarchicalInstance u_fifo (PortConnectionList ( (NamedPortConnection . d ( (NameRef x) )) ))) ;)"
+        );
+        assert_eq!(
+            parse_with("fifo #(32, my_t) u_fifo ();", module_item),
+            "(ModuleInstantiation fifo (ParameterValueAssignment # ( \
+             (OrderedParameterAssignment (Literal 32)) , (OrderedParameterAssignment (NameRef my_t)) )) \
+             (HierarchicalInstance u_fifo (PortConnectionList ( ))) ;)"
+        );
+    }
+
+    #[test]
+    fn test_module_instantiation() {
+        assert_eq!(
+            parse_with("half_adder ha1(.a(x), .b(), y);", module_item),
+            "(ModuleInstantiation half_adder (HierarchicalInstance ha1 (PortConnectionList ( \
+             (NamedPortConnection . a ( (NameRef x) )) , (NamedPortConnection . b ( )) , \
+             (OrderedPortConnection (NameRef y)) ))) ;)"
+        );
+        // Two identifiers without a parenthesis declare a variable of a user type
+        assert_eq!(
+            parse_with("state_t s;", module_item),
+            "(DataDeclaration (DataType state_t) (Declarator s) ;)"
+        );
+    }
+}
@@ -0,0 +1,109 @@
+//! SystemVerilog grammar (IEEE 1800 source text), one function per production.
+//!
+//! Each function parses one production starting at the current token and builds
+//! the matching node. Productions that are optional at a call site are guarded by
+//! the caller checking the leading token.
+
+mod declarations;
+mod expressions;
+mod items;
+mod statements;
+
+pub(crate) use declarations::*;
+pub(crate) use expressions::*;
+pub(crate) use items::*;
+pub(crate) use statements::*;
+
+use crate::parser::Parser;
+use crate::SyntaxKind;
+use svcs_lexer::Token;
+
+/// source_text ::= { description }
+pub(crate) fn source_text(p: &mut Parser) {
+    p.start_node(SyntaxKind::SourceFile);
+    while !p.at_end() {
+        description(p);
+    }
+    p.finish_node();
+}
+
+/// description ::= module_declaration | interface_declaration | program_declaration
+///               | package_declaration | package_item | directive
+fn description(p: &mut Parser) {
+    match p.current() {
+        Some(Token::Module) => design_unit(p, SyntaxKind::ModuleDeclaration, Token::EndModule),
+        Some(Token::Interface) => design_unit(p, SyntaxKind::InterfaceDeclaration, Token::EndInterface),
+        Some(Token::Program) => design_unit(p, SyntaxKind::ProgramDeclaration, Token::EndProgram),
+        Some(Token::Package) => package_declaration(p),
+        Some(Token::PreprocessorDirective) => directive(p),
+        _ if at_package_item(p) => package_item(p),
+        _ => p.error_and_recover(
+            "a module, interface, program or package",
+            &[Token::Module, Token::Interface, Token::Program, Token::Package],
+        ),
+    }
+}
+
+/// Compiler directive with the rest of its line. Directives are kept in the tree
+/// until a preprocessor handles them.
+pub(crate) fn directive(p: &mut Parser) {
+    p.start_node(SyntaxKind::Directive);
+    p.bump();
+    while !p.at_end() && !p.at_line_start() {
+        p.bump();
+    }
+    p.finish_node();
+}
+
+/// package_import_declaration ::= import package_import_item { , package_import_item } ;
+/// package_import_item ::= package_identifier :: identifier | package_identifier :: *
+pub(crate) fn package_import_declaration(p: &mut Parser) {
+    p.start_node(SyntaxKind::PackageImportDeclaration);
+    p.expect(Token::Import);
+    loop {
+        p.start_node(SyntaxKind::PackageImportItem);
+        p.expect(Token::Identifier);
+        p.expect(Token::Scope);
+        if !p.eat(Token::Multiply) {
+            p.expect(Token::Identifier);
+        }
+        p.finish_node();
+        if !p.eat(Token::Comma) {
+            break;
+        }
+    }
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+/// Optional `: name` after an end keyword
+pub(crate) fn end_label(p: &mut Parser) {
+    if p.at(Token::Colon) {
+        p.start_node(SyntaxKind::EndLabel);
+        p.bump();
+        p.expect(Token::Identifier);
+        p.finish_node();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::test_util::parse_with;
+
+    #[test]
+    fn test_descriptions() {
+        assert_eq!(
+            parse_with("`timescale 1ns/1ps\nmodule m; endmodule : m\ninterface i; endinterface", source_text),
+            "(SourceFile (Directive `timescale 1 ns / 1 ps) \
+             (ModuleDeclaration module m ; endmodule (EndLabel : m)) \
+             (InterfaceDeclaration interface i ; endinterface))"
+        );
+        assert_eq!(
+            parse_with("package p; import q::*, r::x; endpackage program t; endprogram", source_text),
+            "(SourceFile (PackageDeclaration package p ; \
+             (PackageImportDeclaration import (PackageImportItem q :: *) , (PackageImportItem r :: x) ;) endpackage) \
+             (ProgramDeclaration program t ; endprogram))"
+        );
+    }
+}
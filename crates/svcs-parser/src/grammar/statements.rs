@@ -0,0 +1,665 @@
+//! Procedural statements.
+
+use super::*;
+
+/// Compound and simple assignment operators of a blocking assignment
+const ASSIGNMENT_OPERATORS: &[Token] = &[
+    Token::Assign, Token::PlusAssign, Token::MinusAssign, Token::MultiplyAssign,
+    Token::DivideAssign, Token::ModuloAssign, Token::AndAssign, Token::OrAssign,
+    Token::XorAssign, Token::LeftShiftAssign, Token::RightShiftAssign,
+];
+
+/// Keywords starting a statement, where recovery from a bad statement stops
+const STATEMENT_START: &[Token] = &[
+    Token::Begin, Token::Fork, Token::If, Token::Case, Token::CaseX, Token::CaseZ, Token::Unique,
+    Token::Unique0, Token::Priority, Token::For, Token::ForEach, Token::While, Token::Do,
+    Token::Repeat, Token::Forever, Token::Return, Token::Break, Token::Continue, Token::Wait,
+    Token::Disable, Token::At, Token::Hash,
+];
+
+/// unique_priority keywords qualifying an `if` or `case`
+const UNIQUE_PRIORITY: &[Token] = &[Token::Unique, Token::Unique0, Token::Priority];
+
+/// Keywords closing a fork
+const JOIN_KEYWORDS: &[Token] = &[Token::Join, Token::JoinAny, Token::JoinNone];
+
+/// Time unit suffixes of a delay value such as `#10ns`
+const TIME_UNITS: &[&str] = &["s", "ms", "us", "ns", "ps", "fs"];
+
+/// block_item_declaration | statement_or_null, as found in blocks and subroutines
+pub(crate) fn block_item(p: &mut Parser) {
+    if p.at_any(&[Token::Parameter, Token::LocalParam]) {
+        parameter_declaration(p);
+    } else if p.at(Token::Typedef) {
+        type_declaration(p);
+    } else if at_data_declaration(p) {
+        data_declaration(p);
+    } else {
+        statement(p);
+    }
+}
+
+/// statement_or_null ::= [ block_identifier : ] statement_item | ;
+pub(crate) fn statement(p: &mut Parser) {
+    match p.current() {
+        Some(Token::Semicolon) => {
+            p.start_node(SyntaxKind::NullStatement);
+            p.bump();
+            p.finish_node();
+        }
+        Some(Token::Identifier) if p.nth_at(1, Token::Colon) => labeled_statement(p),
+        Some(Token::Begin) => seq_block(p),
+        Some(Token::Fork) => par_block(p),
+        Some(Token::Unique | Token::Unique0 | Token::Priority) if p.nth_at(1, Token::If) => conditional_statement(p),
+        Some(Token::If) => conditional_statement(p),
+        Some(Token::Case | Token::CaseX | Token::CaseZ | Token::Unique | Token::Unique0 | Token::Priority) => {
+            case_statement(p)
+        }
+        Some(Token::For) => for_statement(p),
+        Some(Token::ForEach) => foreach_statement(p),
+        Some(Token::While) => while_statement(p),
+        Some(Token::Do) => do_while_statement(p),
+        Some(Token::Repeat) => repeat_statement(p),
+        Some(Token::Forever) => forever_statement(p),
+        Some(Token::At | Token::Hash) => timing_control_statement(p),
+        Some(Token::Return) => return_statement(p),
+        Some(Token::Break | Token::Continue) => jump_statement(p),
+        Some(Token::Wait) => wait_statement(p),
+        Some(Token::Disable) => disable_statement(p),
+        Some(Token::Implication) => event_trigger(p),
+        Some(Token::Increment | Token::Decrement) => inc_dec_statement(p),
+        Some(Token::Identifier | Token::SystemIdentifier | Token::LeftBrace) => assignment_or_call_statement(p),
+        _ if p.at_block_end() => p.error_and_bump("a statement"),
+        _ => p.error_and_recover("a statement", STATEMENT_START),
+    }
+}
+
+/// { block_item } up to a token of `closing` or the end of the design unit.
+/// [`statement`] leaves a closing keyword to its enclosing construct, so one
+/// that cannot close this list, such as an `endcase` in a `begin`, is skipped here.
+pub(crate) fn block_items(p: &mut Parser, closing: &[Token]) {
+    while !p.at_end() && !p.at_any(closing) && !p.at_unit_end() {
+        if p.at_block_end() {
+            p.error_and_recover("a statement", STATEMENT_START);
+        } else {
+            block_item(p);
+        }
+    }
+}
+
+/// block_identifier : statement_item
+fn labeled_statement(p: &mut Parser) {
+    p.start_node(SyntaxKind::LabeledStatement);
+    p.bump();
+    p.bump();
+    statement(p);
+    p.finish_node();
+}
+
+/// seq_block ::= begin [ : identifier ] { block_item } end [ : identifier ]
+pub(crate) fn seq_block(p: &mut Parser) {
+    p.start_node(SyntaxKind::SeqBlock);
+    p.bump();
+    end_label(p);
+    block_items(p, &[Token::End]);
+    p.expect(Token::End);
+    end_label(p);
+    p.finish_node();
+}
+
+/// par_block ::= fork [ : identifier ] { block_item } join_keyword [ : identifier ]
+pub(crate) fn par_block(p: &mut Parser) {
+    p.start_node(SyntaxKind::ParBlock);
+    p.bump();
+    end_label(p);
+    block_items(p, &[Token::Join, Token::JoinAny, Token::JoinNone, Token::End]);
+    if p.at_any(JOIN_KEYWORDS) {
+        p.bump();
+    } else {
+        p.error("join, join_any or join_none");
+    }
+    end_label(p);
+    p.finish_node();
+}
+
+/// conditional_statement ::= [ unique_priority ] if ( expression ) statement_or_null
+///                           [ else statement_or_null ]
+pub(crate) fn conditional_statement(p: &mut Parser) {
+    p.start_node(SyntaxKind::IfStatement);
+    if p.at_any(UNIQUE_PRIORITY) {
+        p.bump();
+    }
+    p.bump();
+    p.expect(Token::LeftParen);
+    expression(p);
+    p.expect(Token::RightParen);
+    statement(p);
+    if p.eat(Token::Else) {
+        statement(p);
+    }
+    p.finish_node();
+}
+
+/// case_statement ::= [ unique_priority ] case_keyword ( expression ) [ inside ]
+///                    case_item { case_item } endcase
+pub(crate) fn case_statement(p: &mut Parser) {
+    p.start_node(SyntaxKind::CaseStatement);
+    if p.at_any(UNIQUE_PRIORITY) {
+        p.bump();
+    }
+    if p.at_any(&[Token::Case, Token::CaseX, Token::CaseZ]) {
+        p.bump();
+    } else {
+        p.error("case, casex or casez");
+    }
+    p.expect(Token::LeftParen);
+    expression(p);
+    p.expect(Token::RightParen);
+    let inside = p.eat(Token::Inside);
+    while !p.at_end() && !p.at(Token::EndCase) && !p.at_unit_end() {
+        if p.at_block_end() {
+            p.error_and_recover("a case item", STATEMENT_START);
+        } else {
+            case_item(p, inside);
+        }
+    }
+    p.expect(Token::EndCase);
+    p.finish_node();
+}
+
+/// case_item ::= expression { , expression } : statement_or_null
+///             | default [ : ] statement_or_null
+/// Items of a `case ... inside` are open value ranges.
+fn case_item(p: &mut Parser, inside: bool) {
+    p.start_node(SyntaxKind::CaseItem);
+    if p.eat(Token::Default) {
+        p.eat(Token::Colon);
+    } else {
+        loop {
+            if inside {
+                open_value_range(p);
+            } else {
+                expression(p);
+            }
+            if !p.eat(Token::Comma) {
+                break;
+            }
+        }
+        p.expect(Token::Colon);
+    }
+    statement(p);
+    p.finish_node();
+}
+
+/// for ( [ for_initialization ] ; [ expression ] ; [ for_step ] ) statement_or_null
+pub(crate) fn for_statement(p: &mut Parser) {
+    p.start_node(SyntaxKind::ForStatement);
+    p.bump();
+    p.expect(Token::LeftParen);
+    if !p.at(Token::Semicolon) {
+        for_initialization(p);
+    }
+    p.expect(Token::Semicolon);
+    if !p.at(Token::Semicolon) {
+        expression(p);
+    }
+    p.expect(Token::Semicolon);
+    if !p.at(Token::RightParen) {
+        for_step(p);
+    }
+    p.expect(Token::RightParen);
+    statement(p);
+    p.finish_node();
+}
+
+/// for_initialization ::= list_of_variable_assignments
+///                      | for_variable_declaration { , for_variable_declaration }
+fn for_initialization(p: &mut Parser) {
+    p.start_node(SyntaxKind::ForInitialization);
+    loop {
+        if at_data_type(p) {
+            p.start_node(SyntaxKind::ForVariableDeclaration);
+            data_type(p);
+            p.expect(Token::Identifier);
+            p.expect(Token::Assign);
+            expression(p);
+            p.finish_node();
+        } else {
+            variable_assignment(p);
+        }
+        if !p.eat(Token::Comma) {
+            break;
+        }
+    }
+    p.finish_node();
+}
+
+/// for_step ::= for_step_assignment { , for_step_assignment }
+fn for_step(p: &mut Parser) {
+    p.start_node(SyntaxKind::ForStep);
+    loop {
+        variable_assignment(p);
+        if !p.eat(Token::Comma) {
+            break;
+        }
+    }
+    p.finish_node();
+}
+
+/// variable_lvalue assignment_operator expression, or an increment/decrement
+pub(crate) fn variable_assignment(p: &mut Parser) {
+    if p.at_any(&[Token::Increment, Token::Decrement]) {
+        p.start_node(SyntaxKind::IncDecExpression);
+        p.bump();
+        postfix_expression(p);
+        p.finish_node();
+        return;
+    }
+    let checkpoint = p.checkpoint();
+    postfix_expression(p);
+    if p.at_any(&[Token::Increment, Token::Decrement]) {
+        p.start_node_at(checkpoint, SyntaxKind::IncDecExpression);
+        p.bump();
+    } else {
+        p.start_node_at(checkpoint, SyntaxKind::VariableAssignment);
+        if p.at_any(ASSIGNMENT_OPERATORS) {
+            p.bump();
+        } else {
+            p.error("an assignment operator");
+        }
+        expression(p);
+    }
+    p.finish_node();
+}
+
+/// foreach ( ps_or_hierarchical_array_identifier [ loop_variables ] ) statement
+pub(crate) fn foreach_statement(p: &mut Parser) {
+    p.start_node(SyntaxKind::ForeachStatement);
+    p.bump();
+    p.expect(Token::LeftParen);
+    let checkpoint = p.checkpoint();
+    name_ref(p);
+    while p.at(Token::Dot) {
+        p.start_node_at(checkpoint, SyntaxKind::MemberAccess);
+        p.bump();
+        p.expect(Token::Identifier);
+        p.finish_node();
+    }
+    loop_variables(p);
+    p.expect(Token::RightParen);
+    statement(p);
+    p.finish_node();
+}
+
+/// loop_variables ::= [ [ identifier ] { , [ identifier ] } ]
+fn loop_variables(p: &mut Parser) {
+    p.start_node(SyntaxKind::LoopVariables);
+    p.expect(Token::LeftBracket);
+    loop {
+        p.eat(Token::Identifier);
+        if !p.eat(Token::Comma) {
+            break;
+        }
+    }
+    p.expect(Token::RightBracket);
+    p.finish_node();
+}
+
+/// while ( expression ) statement_or_null
+pub(crate) fn while_statement(p: &mut Parser) {
+    p.start_node(SyntaxKind::WhileStatement);
+    p.bump();
+    p.expect(Token::LeftParen);
+    expression(p);
+    p.expect(Token::RightParen);
+    statement(p);
+    p.finish_node();
+}
+
+/// do statement_or_null while ( expression ) ;
+pub(crate) fn do_while_statement(p: &mut Parser) {
+    p.start_node(SyntaxKind::DoWhileStatement);
+    p.bump();
+    statement(p);
+    p.expect(Token::While);
+    p.expect(Token::LeftParen);
+    expression(p);
+    p.expect(Token::RightParen);
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+/// repeat ( expression ) statement_or_null
+pub(crate) fn repeat_statement(p: &mut Parser) {
+    p.start_node(SyntaxKind::RepeatStatement);
+    p.bump();
+    p.expect(Token::LeftParen);
+    expression(p);
+    p.expect(Token::RightParen);
+    statement(p);
+    p.finish_node();
+}
+
+/// forever statement_or_null
+pub(crate) fn forever_statement(p: &mut Parser) {
+    p.start_node(SyntaxKind::ForeverStatement);
+    p.bump();
+    statement(p);
+    p.finish_node();
+}
+
+/// procedural_timing_control_statement ::= procedural_timing_control statement_or_null
+pub(crate) fn timing_control_statement(p: &mut Parser) {
+    p.start_node(SyntaxKind::TimingControlStatement);
+    timing_control(p);
+    statement(p);
+    p.finish_node();
+}
+
+/// procedural_timing_control ::= delay_control | event_control
+pub(crate) fn timing_control(p: &mut Parser) {
+    if p.at(Token::Hash) {
+        delay_control(p);
+    } else {
+        event_control(p);
+    }
+}
+
+/// delay_control ::= # delay_value | # ( mintypmax_expression )
+pub(crate) fn delay_control(p: &mut Parser) {
+    p.start_node(SyntaxKind::DelayControl);
+    p.expect(Token::Hash);
+    delay_value(p);
+    p.finish_node();
+}
+
+/// delay_value, a parenthesized expression included, with an optional time unit
+pub(crate) fn delay_value(p: &mut Parser) {
+    let number = p.at_any(&[Token::UnsizedNumber, Token::RealNumber]);
+    primary(p);
+    if number && p.at(Token::Identifier) && p.joined() && TIME_UNITS.contains(&p.nth_text(0)) {
+        p.bump();
+    }
+}
+
+/// event_control ::= @ hierarchical_event_identifier | @ ( event_expression ) | @* | @ (*)
+pub(crate) fn event_control(p: &mut Parser) {
+    p.start_node(SyntaxKind::EventControl);
+    p.expect(Token::At);
+    if p.at(Token::Multiply) {
+        p.bump();
+    } else if p.eat(Token::LeftParen) {
+        if !p.eat(Token::Multiply) {
+            loop {
+                event_expression(p);
+                if !p.at_any(&[Token::Or, Token::Comma]) {
+                    break;
+                }
+                p.bump();
+            }
+        }
+        p.expect(Token::RightParen);
+    } else {
+        name_ref(p);
+    }
+    p.finish_node();
+}
+
+/// event_expression ::= [ edge_identifier ] expression [ iff expression ]
+fn event_expression(p: &mut Parser) {
+    p.start_node(SyntaxKind::EventExpression);
+    if p.at_any(&[Token::PosEdge, Token::NegEdge, Token::Edge]) {
+        p.bump();
+    }
+    expression(p);
+    if p.eat(Token::Iff) {
+        expression(p);
+    }
+    p.finish_node();
+}
+
+/// return [ expression ] ;
+pub(crate) fn return_statement(p: &mut Parser) {
+    p.start_node(SyntaxKind::ReturnStatement);
+    p.bump();
+    if !p.at(Token::Semicolon) {
+        expression(p);
+    }
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+/// break ; | continue ;
+pub(crate) fn jump_statement(p: &mut Parser) {
+    p.start_node(SyntaxKind::JumpStatement);
+    p.bump();
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+/// wait_statement ::= wait ( expression ) statement_or_null | wait fork ;
+pub(crate) fn wait_statement(p: &mut Parser) {
+    p.start_node(SyntaxKind::WaitStatement);
+    p.bump();
+    if p.eat(Token::Fork) {
+        p.expect(Token::Semicolon);
+    } else {
+        p.expect(Token::LeftParen);
+        expression(p);
+        p.expect(Token::RightParen);
+        statement(p);
+    }
+    p.finish_node();
+}
+
+/// disable_statement ::= disable hierarchical_identifier ; | disable fork ;
+pub(crate) fn disable_statement(p: &mut Parser) {
+    p.start_node(SyntaxKind::DisableStatement);
+    p.bump();
+    if !p.eat(Token::Fork) {
+        postfix_expression(p);
+    }
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+/// event_trigger ::= -> hierarchical_event_identifier ;
+///                 | ->> [ delay_or_event_control ] hierarchical_event_identifier ;
+/// `->>` lexes as `->` directly followed by `>`.
+pub(crate) fn event_trigger(p: &mut Parser) {
+    p.start_node(SyntaxKind::EventTrigger);
+    p.bump();
+    if p.at(Token::GreaterThan) && p.joined() {
+        p.bump();
+        if p.at_any(&[Token::Hash, Token::At]) {
+            timing_control(p);
+        }
+    }
+    postfix_expression(p);
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+/// inc_or_dec_expression ; with the operator first
+fn inc_dec_statement(p: &mut Parser) {
+    p.start_node(SyntaxKind::ExpressionStatement);
+    variable_assignment(p);
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+/// blocking_assignment ::= variable_lvalue assignment_operator expression ;
+/// nonblocking_assignment ::= variable_lvalue <= [ delay_or_event_control ] expression ;
+/// subroutine_call_statement ::= subroutine_call ;
+/// A trailing `++` or `--` makes an increment statement.
+fn assignment_or_call_statement(p: &mut Parser) {
+    let checkpoint = p.checkpoint();
+    postfix_expression(p);
+    if p.at_any(&[Token::Increment, Token::Decrement]) {
+        p.start_node_at(checkpoint, SyntaxKind::IncDecExpression);
+        p.bump();
+        p.finish_node();
+        p.start_node_at(checkpoint, SyntaxKind::ExpressionStatement);
+    } else if p.at(Token::LessEqual) {
+        p.start_node_at(checkpoint, SyntaxKind::NonblockingAssignment);
+        p.bump();
+        if p.at_any(&[Token::Hash, Token::At]) {
+            timing_control(p);
+        }
+        expression(p);
+    } else if p.at_any(ASSIGNMENT_OPERATORS) {
+        p.start_node_at(checkpoint, SyntaxKind::BlockingAssignment);
+        p.bump();
+        if p.at_any(&[Token::Hash, Token::At]) {
+            timing_control(p);
+        }
+        expression(p);
+    } else {
+        p.start_node_at(checkpoint, SyntaxKind::SubroutineCallStatement);
+    }
+    p.expect(Token::Semicolon);
+    p.finish_node();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::test_util::parse_with;
+
+    #[test]
+    fn test_blocks_and_assignments() {
+        assert_eq!(
+            parse_with("begin : b int i; i = 0; q <= #1 d; x += 2; $display(i); ; end : b", statement),
+            "(SeqBlock begin (EndLabel : b) (DataDeclaration (DataType int) (Declarator i) ;) \
+             (BlockingAssignment (NameRef i) = (Literal 0) ;) \
+             (NonblockingAssignment (NameRef q) <= (DelayControl # (Literal 1)) (NameRef d) ;) \
+             (BlockingAssignment (NameRef x) += (Literal 2) ;) \
+             (SubroutineCallStatement (CallExpression (NameRef $display) (ArgumentList ( (NameRef i) ))) ;) \
+             (NullStatement ;) end (EndLabel : b))"
+        );
+    }
+
+    #[test]
+    fn test_conditional_and_case() {
+        assert_eq!(
+            parse_with("if (a) b = 1; else if (c) b = 2; else ;", statement),
+            "(IfStatement if ( (NameRef a) ) (BlockingAssignment (NameRef b) = (Literal 1) ;) else \
+             (IfStatement if ( (NameRef c) ) (BlockingAssignment (NameRef b) = (Literal 2) ;) else (NullStatement ;)))"
+        );
+        assert_eq!(
+            parse_with("casez (s) 2'b1z: y = a; endcase", statement),
+            "(CaseStatement casez ( (NameRef s) ) \
+             (CaseItem (Literal 2'b1z) : (BlockingAssignment (NameRef y) = (NameRef a) ;)) endcase)"
+        );
+        assert_eq!(
+            parse_with("case (s) 0, 1: y = a; default: y = b; endcase", statement),
+            "(CaseStatement case ( (NameRef s) ) \
+             (CaseItem (Literal 0) , (Literal 1) : (BlockingAssignment (NameRef y) = (NameRef a) ;)) \
+             (CaseItem default : (BlockingAssignment (NameRef y) = (NameRef b) ;)) endcase)"
+        );
+    }
+
+    #[test]
+    fn test_loops() {
+        assert_eq!(
+            parse_with("for (int i = 0; i < 4; i++) x = i;", statement),
+            "(ForStatement for ( (ForInitialization (ForVariableDeclaration (DataType int) i = (Literal 0))) ; \
+             (BinaryExpression (NameRef i) < (Literal 4)) ; (ForStep (IncDecExpression (NameRef i) ++)) ) \
+             (BlockingAssignment (NameRef x) = (NameRef i) ;))"
+        );
+        assert_eq!(
+            parse_with("while (busy) repeat (3) forever ;", statement),
+            "(WhileStatement while ( (NameRef busy) ) (RepeatStatement repeat ( (Literal 3) ) \
+             (ForeverStatement forever (NullStatement ;))))"
+        );
+    }
+
+    #[test]
+    fn test_timing_controls() {
+        assert_eq!(
+            parse_with("@(posedge clk or negedge rst_n, x) #10ns @* @(*) @ev return;", statement),
+            "(TimingControlStatement (EventControl @ ( (EventExpression posedge (NameRef clk)) or \
+             (EventExpression negedge (NameRef rst_n)) , (EventExpression (NameRef x)) )) \
+             (TimingControlStatement (DelayControl # (Literal 10) ns) \
+             (TimingControlStatement (EventControl @ *) (TimingControlStatement (EventControl @ ( * )) \
+             (TimingControlStatement (EventControl @ (NameRef ev)) (ReturnStatement return ;))))))"
+        );
+        assert_eq!(parse_with("break;", statement), "(JumpStatement break ;)");
+        assert_eq!(
+            parse_with("@(posedge clk iff en) ;", statement),
+            "(TimingControlStatement (EventControl @ ( (EventExpression posedge (NameRef clk) iff (NameRef en)) )) \
+             (NullStatement ;))"
+        );
+    }
+
+    #[test]
+    fn test_unique_priority() {
+        assert_eq!(
+            parse_with("unique if (a) x = 1; else x = 2;", statement),
+            "(IfStatement unique if ( (NameRef a) ) (BlockingAssignment (NameRef x) = (Literal 1) ;) else \
+             (BlockingAssignment (NameRef x) = (Literal 2) ;))"
+        );
+        assert_eq!(
+            parse_with("priority casex (s) 2'b1x: ; endcase", statement),
+            "(CaseStatement priority casex ( (NameRef s) ) (CaseItem (Literal 2'b1x) : (NullStatement ;)) endcase)"
+        );
+        assert_eq!(
+            parse_with("unique0 case (s) inside [0:3], 7: ; endcase", statement),
+            "(CaseStatement unique0 case ( (NameRef s) ) inside \
+             (CaseItem (ValueRange [ (Literal 0) : (Literal 3) ]) , (Literal 7) : (NullStatement ;)) endcase)"
+        );
+    }
+
+    #[test]
+    fn test_labels_and_fork_join() {
+        assert_eq!(
+            parse_with("blk: begin end", statement),
+            "(LabeledStatement blk : (SeqBlock begin end))"
+        );
+        assert_eq!(
+            parse_with("fork : f a = 1; #5 b = 2; join_any : f", statement),
+            "(ParBlock fork (EndLabel : f) (BlockingAssignment (NameRef a) = (Literal 1) ;) \
+             (TimingControlStatement (DelayControl # (Literal 5)) (BlockingAssignment (NameRef b) = (Literal 2) ;)) \
+             join_any (EndLabel : f))"
+        );
+        assert_eq!(parse_with("fork join_none", statement), "(ParBlock fork join_none)");
+    }
+
+    #[test]
+    fn test_other_loops() {
+        assert_eq!(
+            parse_with("do i++; while (i < 4);", statement),
+            "(DoWhileStatement do (ExpressionStatement (IncDecExpression (NameRef i) ++) ;) while ( \
+             (BinaryExpression (NameRef i) < (Literal 4)) ) ;)"
+        );
+        assert_eq!(
+            parse_with("foreach (m.arr[i, , k]) --n;", statement),
+            "(ForeachStatement foreach ( (MemberAccess (NameRef m) . arr) (LoopVariables [ i , , k ]) ) \
+             (ExpressionStatement (IncDecExpression -- (NameRef n)) ;))"
+        );
+    }
+
+    #[test]
+    fn test_wait_disable_and_trigger() {
+        assert_eq!(
+            parse_with("wait (done) ;", statement),
+            "(WaitStatement wait ( (NameRef done) ) (NullStatement ;))"
+        );
+        assert_eq!(parse_with("wait fork;", statement), "(WaitStatement wait fork ;)");
+        assert_eq!(parse_with("disable fork;", statement), "(DisableStatement disable fork ;)");
+        assert_eq!(
+            parse_with("disable top.blk;", statement),
+            "(DisableStatement disable (MemberAccess (NameRef top) . blk) ;)"
+        );
+        assert_eq!(parse_with("-> ev;", statement), "(EventTrigger -> (NameRef ev) ;)");
+        assert_eq!(parse_with("->> ev;", statement), "(EventTrigger -> > (NameRef ev) ;)");
+        assert_eq!(
+            parse_with("->> #5 top.done;", statement),
+            "(EventTrigger -> > (DelayControl # (Literal 5)) (MemberAccess (NameRef top) . done) ;)"
+        );
+        assert_eq!(
+            parse_with("->> @(posedge clk) ev;", statement),
+            "(EventTrigger -> > (EventControl @ ( (EventExpression posedge (NameRef clk)) )) (NameRef ev) ;)"
+        );
+    }
+}
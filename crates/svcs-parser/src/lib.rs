@@ -1,396 +1,445 @@
-use svcs_lexer::Token;
-use std::ops::Range;
+pub mod ast;
+mod error;
+mod grammar;
+mod parser;
+mod syntax_kind;
+
+pub use error::{Expected, ParseError, ParseErrors};
+pub use syntax_kind::SyntaxKind;
+
+use parser::Parser;
+use rowan::{GreenNode, TextSize, WalkEvent};
+use svcs_lexer::utils::LineIndex;
+use svcs_lexer::{Interner, SpannedToken, Symbol};
+use svcs_source::SourceFile;
 use std::fmt;
 
-#[derive(Debug)]
-pub struct CstNode {
-    pub node_type: String, // semantic type: Port, AssignStatement, Instance, etc.
-    pub kind: String,      // token kind or node label
-    pub lexeme: String,
-    pub line: usize,
-    pub column: usize,
-    pub children: Vec<CstNode>,
+/// Tags the rowan tree with [`SyntaxKind`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SystemVerilog {}
+
+impl rowan::Language for SystemVerilog {
+    type Kind = SyntaxKind;
+
+    fn kind_from_raw(raw: rowan::SyntaxKind) -> SyntaxKind {
+        SyntaxKind::from_raw(raw.0)
+    }
+
+    fn kind_to_raw(kind: SyntaxKind) -> rowan::SyntaxKind {
+        kind.into()
+    }
+}
+
+impl From<SyntaxKind> for rowan::SyntaxKind {
+    fn from(kind: SyntaxKind) -> Self {
+        rowan::SyntaxKind(kind as u16)
+    }
 }
 
-#[derive(Debug)]
+/// Node of the lossless tree, with parent pointers and a text range
+pub type SyntaxNode = rowan::SyntaxNode<SystemVerilog>;
+pub type SyntaxToken = rowan::SyntaxToken<SystemVerilog>;
+pub type SyntaxElement = rowan::SyntaxElement<SystemVerilog>;
+
+/// Lossless concrete syntax tree of one file. The tree itself is immutable and
+/// can be sent across threads; [`syntax`](Cst::syntax) gives a navigable root.
+#[derive(Debug, Clone)]
 pub struct Cst {
-    pub root: CstNode,
+    green: GreenNode,
+    /// Interned names of identifier tokens by start offset, sorted
+    symbols: Vec<(TextSize, Symbol)>,
 }
 
+impl Cst {
+    pub fn syntax(&self) -> SyntaxNode {
+        SyntaxNode::new_root(self.green.clone())
+    }
 
-/// Compute line and column from a byte offset in the input string
-fn get_line_col(input: &str, offset: usize) -> (usize, usize) {
-    let mut line = 1;
-    let mut col = 1;
-    let mut count = 0;
-    for c in input.chars() {
-        if count == offset {
-            break;
-        }
-        if c == '\n' {
-            line += 1;
-            col = 1;
-        } else {
-            col += 1;
-        }
-        count += c.len_utf8();
+    /// Interned name of an identifier token when parsed with an [`Interner`]
+    pub fn symbol(&self, token: &SyntaxToken) -> Option<Symbol> {
+        let start = token.text_range().start();
+        self.symbols
+            .binary_search_by_key(&start, |&(offset, _)| offset)
+            .ok()
+            .map(|index| self.symbols[index].1)
     }
-    (line, col)
 }
 
-pub fn parse_tokens_with_spans(input: &str, tokens: &[(Token, Range<usize>)]) -> Result<Cst, String> {
-    let mut children = Vec::new();
-    let mut i = 0;
-    while i < tokens.len() {
-        let (ref tok, ref span) = tokens[i];
-        let lexeme = input.get(span.clone()).unwrap_or("").to_string();
-        let (line, column) = get_line_col(input, span.start);
-        if *tok == Token::Module {
-            // Start of module
-            let mut module_children = Vec::new();
-            // Add the 'module' keyword
-            module_children.push(CstNode {
-                node_type: "Keyword".to_string(),
-                kind: format!("{:?}", tok),
-                lexeme: lexeme.clone(),
-                line,
-                column,
-                children: Vec::new(),
-            });
-            i += 1;
-            // Identifier (module name)
-            if i < tokens.len() && tokens[i].0 == Token::Identifier {
-                // (tspan assignment removed; inlined below)
-                module_children.push(CstNode {
-                    node_type: "ModuleName".to_string(),
-                    kind: format!("{:?}", &tokens[i].0),
-                    lexeme: input.get(tokens[i].1.clone()).unwrap_or("").to_string(),
-                    line: get_line_col(input, tokens[i].1.start).0,
-                    column: get_line_col(input, tokens[i].1.start).1,
-                    children: Vec::new(),
-                });
-                i += 1;
-            }
-            // Port list (optional)
-            if i < tokens.len() && tokens[i].0 == Token::LeftParen {
-                let mut port_children = Vec::new();
-                // LeftParen
-                // (tspan assignment removed; inlined below)
-                i += 1;
-                // Group ports: (input/output/inout ... Identifier ... ,)
-                while i < tokens.len() && tokens[i].0 != Token::RightParen {
-                    let _start = i;
-                    if matches!(tokens[i].0, Token::Input | Token::Output | Token::Inout) {
-                        let dir_token = &tokens[i];
-                        let (dir, dir_span) = dir_token;
-                        let dir_lex = input.get(dir_span.clone()).unwrap_or("").to_string();
-                        let (dir_line, dir_col) = get_line_col(input, dir_span.start);
-                        i += 1;
-                        // Identifier
-                        if i < tokens.len() && tokens[i].0 == Token::Identifier {
-                            let (ref id, ref id_span) = tokens[i];
-                            let id_lex = input.get(id_span.clone()).unwrap_or("").to_string();
-                            let (id_line, id_col) = get_line_col(input, id_span.start);
-                            i += 1;
-                            // Optional comma
-                            let mut comma_child = None;
-                            if i < tokens.len() && tokens[i].0 == Token::Comma {
-                                let (ref c, ref cspan) = tokens[i];
-                                let c_lex = input.get(cspan.clone()).unwrap_or("").to_string();
-                                let (c_line, c_col) = get_line_col(input, cspan.start);
-                                comma_child = Some(CstNode {
-                                    node_type: "Comma".to_string(),
-                                    kind: format!("{:?}", c),
-                                    lexeme: c_lex,
-                                    line: c_line,
-                                    column: c_col,
-                                    children: Vec::new(),
-                                });
-                                i += 1;
-                            }
-                            let mut port_kids = vec![
-                                CstNode {
-                                    node_type: "Direction".to_string(),
-                                    kind: format!("{:?}", dir),
-                                    lexeme: dir_lex,
-                                    line: dir_line,
-                                    column: dir_col,
-                                    children: Vec::new(),
-                                },
-                                CstNode {
-                                    node_type: "PortName".to_string(),
-                                    kind: format!("{:?}", id),
-                                    lexeme: id_lex,
-                                    line: id_line,
-                                    column: id_col,
-                                    children: Vec::new(),
-                                },
-                            ];
-                            if let Some(comma) = comma_child { port_kids.push(comma); }
-                            port_children.push(CstNode {
-                                node_type: "Port".to_string(),
-                                kind: "Port".to_string(),
-                                lexeme: String::new(),
-                                line: 0,
-                                column: 0,
-                                children: port_kids,
-                            });
-                            continue;
-                        }
-                    }
-                    // Fallback: add as generic token
-                    // Do not assign unused variables, just use directly in CstNode
-                    let tspan = &tokens[i].1;
-                    port_children.push(CstNode {
-                        node_type: "Token".to_string(),
-                        kind: format!("{:?}", &tokens[i].0),
-                        lexeme: input.get(tspan.clone()).unwrap_or("").to_string(),
-                        line: get_line_col(input, tspan.start).0,
-                        column: get_line_col(input, tspan.start).1,
-                        children: Vec::new(),
-                    });
-                    i += 1;
-                }
-                // RightParen
-                if i < tokens.len() && tokens[i].0 == Token::RightParen {
-                    let tspan = &tokens[i].1;
-                    i += 1;
-                    port_children.push(CstNode {
-                        node_type: "Paren".to_string(),
-                        kind: format!("{:?}", &tokens[i].0),
-                        lexeme: input.get(tspan.clone()).unwrap_or("").to_string(),
-                        line: get_line_col(input, tspan.start).0,
-                        column: get_line_col(input, tspan.start).1,
-                        children: Vec::new(),
-                    });
-                }
-                module_children.push(CstNode {
-                    node_type: "PortList".to_string(),
-                    kind: "PortList".to_string(),
-                    lexeme: String::new(),
-                    line: 0,
-                    column: 0,
-                    children: port_children,
-                });
-            }
-            // Semicolon
-            if i < tokens.len() && tokens[i].0 == Token::Semicolon {
-                let (ref t, ref tspan) = tokens[i];
-                let tlex = input.get(tspan.clone()).unwrap_or("").to_string();
-                let (tline, tcol) = get_line_col(input, tspan.start);
-                module_children.push(CstNode {
-                    node_type: "Semicolon".to_string(),
-                    kind: format!("{:?}", t),
-                    lexeme: tlex,
-                    line: tline,
-                    column: tcol,
-                    children: Vec::new(),
-                });
-                i += 1;
-            }
-            // Module body (everything until EndModule)
-            let mut body_children = Vec::new();
-            while i < tokens.len() && tokens[i].0 != Token::EndModule {
-                // Assign statement: AssignKeyword Identifier Assign ... Semicolon
-                if i+4 <= tokens.len() && tokens[i].0 == Token::AssignKeyword && tokens[i+1].0 == Token::Identifier && tokens[i+2].0 == Token::Assign {
-                    let mut assign_kids = Vec::new();
-                    for j in 0..5 { // assign, id, =, expr, ;
-                        let (ref _t, ref tspan) = &tokens[i+j];
-                        let tlex = input.get(tspan.clone()).unwrap_or("").to_string();
-                        let (tline, tcol) = get_line_col(input, tspan.start);
-                        assign_kids.push(CstNode {
-                            node_type: if j==0 {"AssignKeyword".to_string()} else if j==1 {"LHS".to_string()} else if j==2 {"AssignOp".to_string()} else if j==4 {"Semicolon".to_string()} else {"Expr".to_string()},
-                            kind: format!("{:?}", &tokens[i+j].0),
-                            lexeme: tlex,
-                            line: tline,
-                            column: tcol,
-                            children: Vec::new(),
-                        });
-                    }
-                    body_children.push(CstNode {
-                        node_type: "AssignStatement".to_string(),
-                        kind: "AssignStatement".to_string(),
-                        lexeme: String::new(),
-                        line: 0,
-                        column: 0,
-                        children: assign_kids,
-                    });
-                    i += 5;
-                    continue;
-                }
-                // Instance: Identifier Identifier LeftParen ... RightParen Semicolon
-                if i+5 <= tokens.len() && tokens[i].0 == Token::Identifier && tokens[i+1].0 == Token::Identifier && tokens[i+2].0 == Token::LeftParen {
-                    let mut inst_kids = Vec::new();
-                    for j in 0..2 { // module name, instance name
-                        let (ref _t, ref tspan) = &tokens[i+j];
-                        let tlex = input.get(tspan.clone()).unwrap_or("").to_string();
-                        let (tline, tcol) = get_line_col(input, tspan.start);
-                        inst_kids.push(CstNode {
-                            node_type: if j==0 {"ModuleType".to_string()} else {"InstanceName".to_string()},
-                            kind: format!("{:?}", &tokens[i+j].0),
-                            lexeme: tlex,
-                            line: tline,
-                            column: tcol,
-                            children: Vec::new(),
-                        });
-                    }
-                    // Port connections (everything inside parens)
-                    let mut portcon_kids = Vec::new();
-                    let mut k = i+3;
-                    while k < tokens.len() && tokens[k].0 != Token::RightParen {
-                        let (ref t, ref tspan) = tokens[k];
-                        let tlex = input.get(tspan.clone()).unwrap_or("").to_string();
-                        let (tline, tcol) = get_line_col(input, tspan.start);
-                        portcon_kids.push(CstNode {
-                            node_type: "PortConnection".to_string(),
-                            kind: format!("{:?}", t),
-                            lexeme: tlex,
-                            line: tline,
-                            column: tcol,
-                            children: Vec::new(),
-                        });
-                        k += 1;
-                    }
-                    // RightParen
-                    if k < tokens.len() && tokens[k].0 == Token::RightParen {
-                        let (ref t, ref tspan) = tokens[k];
-                        let tlex = input.get(tspan.clone()).unwrap_or("").to_string();
-                        let (tline, tcol) = get_line_col(input, tspan.start);
-                        portcon_kids.push(CstNode {
-                            node_type: "Paren".to_string(),
-                            kind: format!("{:?}", t),
-                            lexeme: tlex,
-                            line: tline,
-                            column: tcol,
-                            children: Vec::new(),
-                        });
-                        k += 1;
-                    }
-                    // Semicolon
-                    if k < tokens.len() && tokens[k].0 == Token::Semicolon {
-                        let (ref t, ref tspan) = tokens[k];
-                        let tlex = input.get(tspan.clone()).unwrap_or("").to_string();
-                        let (tline, tcol) = get_line_col(input, tspan.start);
-                        portcon_kids.push(CstNode {
-                            node_type: "Semicolon".to_string(),
-                            kind: format!("{:?}", t),
-                            lexeme: tlex,
-                            line: tline,
-                            column: tcol,
-                            children: Vec::new(),
-                        });
-                        k += 1;
-                    }
-                    inst_kids.push(CstNode {
-                        node_type: "PortConnections".to_string(),
-                        kind: "PortConnections".to_string(),
-                        lexeme: String::new(),
-                        line: 0,
-                        column: 0,
-                        children: portcon_kids,
-                    });
-                    body_children.push(CstNode {
-                        node_type: "Instance".to_string(),
-                        kind: "Instance".to_string(),
-                        lexeme: String::new(),
-                        line: 0,
-                        column: 0,
-                        children: inst_kids,
-                    });
-                    i = k;
-                    continue;
-                }
-                // Fallback: generic token
-                let (ref t, ref tspan) = tokens[i];
-                let tlex = input.get(tspan.clone()).unwrap_or("").to_string();
-                let (tline, tcol) = get_line_col(input, tspan.start);
-                body_children.push(CstNode {
-                    node_type: "Token".to_string(),
-                    kind: format!("{:?}", t),
-                    lexeme: tlex,
-                    line: tline,
-                    column: tcol,
-                    children: Vec::new(),
-                });
-                i += 1;
-            }
-            if !body_children.is_empty() {
-                module_children.push(CstNode {
-                    node_type: "ModuleBody".to_string(),
-                    kind: "ModuleBody".to_string(),
-                    lexeme: String::new(),
-                    line: 0,
-                    column: 0,
-                    children: body_children,
-                });
-            }
-            // EndModule
-            if i < tokens.len() && tokens[i].0 == Token::EndModule {
-                let (ref t, ref tspan) = tokens[i];
-                let tlex = input.get(tspan.clone()).unwrap_or("").to_string();
-                let (tline, tcol) = get_line_col(input, tspan.start);
-                module_children.push(CstNode {
-                    node_type: "Keyword".to_string(),
-                    kind: format!("{:?}", t),
-                    lexeme: tlex,
-                    line: tline,
-                    column: tcol,
-                    children: Vec::new(),
-                });
-                i += 1;
-            }
-            // Wrap into a ModuleDeclaration CST node
-            children.push(CstNode {
-                node_type: "ModuleDeclaration".to_string(),
-                kind: "ModuleDeclaration".to_string(),
-                lexeme: String::new(),
-                line: 0,
-                column: 0,
-                children: module_children,
-            });
+/// A tree together with the syntax errors found while building it. The tree is
+/// complete even when there are errors: skipped tokens sit in `Error` nodes and
+/// the text still round-trips.
+#[derive(Debug, Clone)]
+pub struct Parse {
+    pub cst: Cst,
+    pub errors: Vec<ParseError>,
+}
+
+impl Parse {
+    /// The tree if the input had no syntax errors
+    pub fn ok(self) -> Result<Cst, ParseErrors> {
+        if self.errors.is_empty() {
+            Ok(self.cst)
         } else {
-            // Fallback: tokens outside modules
-            children.push(CstNode {
-                node_type: "Token".to_string(),
-                kind: format!("{:?}", tok),
-                lexeme,
-                line,
-                column,
-                children: Vec::new(),
-            });
-            i += 1;
+            Err(ParseErrors(self.errors))
         }
     }
-    Ok(Cst {
-        root: CstNode {
-            node_type: "SourceFile".to_string(),
-            kind: "SourceFile".to_string(),
-            lexeme: String::new(),
-            line: 0,
-            column: 0,
-            children,
-        },
-    })
 }
 
+/// Parse the token stream of a source file into a CST, recovering from and
+/// reporting every syntax error
+pub fn parse_tokens(source: &SourceFile, tokens: &[SpannedToken]) -> Parse {
+    parse_token_stream(source, tokens.iter().cloned())
+}
 
+/// Parse and attach interned symbols to identifier tokens, so names can be
+/// compared across files without string comparison. Names are interned as
+/// their tokens are added to the tree, straight from the source text.
+pub fn parse_tokens_with_interner(source: &SourceFile, tokens: &[SpannedToken], interner: &Interner) -> Parse {
+    parse_token_stream_with_interner(source, tokens.iter().cloned(), interner)
+}
+
+/// Parse tokens as they come, for example straight from the lexer. Only the
+/// tokens the grammar looks ahead at are buffered.
+pub fn parse_token_stream<'a>(source: &'a SourceFile, tokens: impl IntoIterator<Item = SpannedToken> + 'a) -> Parse {
+    parse(Parser::new(source, tokens))
+}
+
+/// [`parse_token_stream`] with interned identifier symbols, as in
+/// [`parse_tokens_with_interner`]
+pub fn parse_token_stream_with_interner<'a>(
+    source: &'a SourceFile,
+    tokens: impl IntoIterator<Item = SpannedToken> + 'a,
+    interner: &'a Interner,
+) -> Parse {
+    parse(Parser::new(source, tokens).with_interner(interner))
+}
+
+fn parse(mut parser: Parser) -> Parse {
+    grammar::source_text(&mut parser);
+    let (green, symbols, errors) = parser.finish();
+    Parse { cst: Cst { green, symbols }, errors }
+}
+
+/// Parse the token stream of a source file into a CST, failing with all syntax
+/// errors of the file
+pub fn parse_tokens_with_spans(source: &SourceFile, tokens: &[SpannedToken]) -> Result<Cst, ParseErrors> {
+    parse_tokens(source, tokens).ok()
+}
+
+/// One line per node (`Kind  start..end`) and token (`Kind  "text"  @line:column`),
+/// indented by depth; trivia is left out
 impl fmt::Display for Cst {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fn fmt_node(node: &CstNode, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
-            for _ in 0..indent {
-                write!(f, "  ")?;
-            }
-            if node.kind == "ModuleDeclaration" || node.kind == "SourceFile" {
-                writeln!(f, "{}", node.kind)?;
-            } else {
-                writeln!(f, "{}  \"{}\"  @{}:{}", node.kind, node.lexeme, node.line, node.column)?;
-            }
-            for child in &node.children {
-                fmt_node(child, f, indent + 1)?;
+        let root = self.syntax();
+        let text = root.to_string();
+        let lines = LineIndex::new(&text);
+        let mut depth = 0;
+        for event in root.preorder_with_tokens() {
+            match event {
+                WalkEvent::Enter(element) => {
+                    if element.kind().is_trivia() {
+                        continue;
+                    }
+                    write!(f, "{:width$}", "", width = depth * 2)?;
+                    match &element {
+                        rowan::NodeOrToken::Node(node) => {
+                            writeln!(f, "{}  {:?}", node.kind(), node.text_range())?;
+                            depth += 1;
+                        }
+                        rowan::NodeOrToken::Token(token) => {
+                            let (line, column) = lines.line_col(&text, token.text_range().start().into());
+                            writeln!(f, "{}  \"{}\"  @{}:{}", token.kind(), token.text(), line, column)?;
+                        }
+                    }
+                }
+                WalkEvent::Leave(rowan::NodeOrToken::Node(_)) => depth -= 1,
+                WalkEvent::Leave(_) => {}
             }
-            Ok(())
         }
-        fmt_node(&self.root, f, 0)
+        Ok(())
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ast::{AstNode, HasName, SourceFile};
+    use svcs_lexer::create_default_lexer;
+    use svcs_source::{FileId, SourceDb};
+
+    /// An in-memory file holding `input`, with its tokens
+    fn lexed(path: &str, input: &str) -> (SourceDb, FileId, Vec<SpannedToken>) {
+        let mut db = SourceDb::new();
+        let file = db.add_file(path, input);
+        let tokens = create_default_lexer(input, path.to_string()).unwrap().tokenize().unwrap();
+        (db, file, tokens)
+    }
+
+    #[test]
+    fn test_token_stream_parses_like_slice() {
+        let input = "module m #(parameter W = 8) (input a);\n  u #(.N(W)) i(.a(a));\n  x = (a b;\nendmodule\n";
+        let (db, file, tokens) = lexed("test.sv", input);
+        let collected = parse_tokens(db.file(file), &tokens);
+
+        let lexer = create_default_lexer(input, "test.sv".to_string()).unwrap();
+        let streamed = parse_token_stream(db.file(file), lexer.map(Result::unwrap));
+        assert_eq!(streamed.cst.syntax().to_string(), input);
+        assert_eq!(streamed.cst.to_string(), collected.cst.to_string());
+        assert_eq!(streamed.errors, collected.errors);
+    }
+
+    #[test]
+    fn test_identifier_symbols_shared_across_files() {
+        let interner = Interner::new();
+        let parse = |input: &str| {
+            let (db, file, tokens) = lexed("test.sv", input);
+            parse_tokens_with_interner(db.file(file), &tokens, &interner).ok().unwrap()
+        };
+        let a = parse("module half_adder(input a); endmodule");
+        let b = parse("module top(input a); half_adder ha(.a(a)); endmodule");
+
+        let module_a = SourceFile::cast(a.syntax()).unwrap().modules().next().unwrap();
+        let module_b = SourceFile::cast(b.syntax()).unwrap().modules().next().unwrap();
+        let port_a = module_a.ports().next().unwrap().name_token().unwrap();
+        let port_b = module_b.ports().next().unwrap().name_token().unwrap();
+        assert!(a.symbol(&port_a).is_some());
+        assert_eq!(a.symbol(&port_a), b.symbol(&port_b));
+
+        let module_name = a.symbol(&module_a.name_token().unwrap()).unwrap();
+        let instance_type = module_b.instantiations().next().unwrap().module_name_token().unwrap();
+        assert_eq!(Some(module_name), b.symbol(&instance_type));
+        assert_eq!(&*interner.resolve(module_name), "half_adder");
+    }
+
+    #[test]
+    fn test_protected_module_is_black_box() {
+        let input = "module ip(input clk, output q);\n\
+            `pragma protect begin_protected\n\
+            `pragma protect data_block\n\
+            cXVpY2sgYnJvd24gZm94\n\
+            `pragma protect end_protected\n\
+            endmodule";
+        let (db, file, tokens) = lexed("ip.sv", input);
+        let cst = parse_tokens_with_spans(db.file(file), &tokens).unwrap();
+
+        let module = SourceFile::cast(cst.syntax()).unwrap().modules().next().unwrap();
+        assert!(module.is_black_box());
+        let ports: Vec<_> = module.ports().filter_map(|port| port.name()).collect();
+        assert_eq!(ports, ["clk", "q"]);
+    }
+
+    #[test]
+    fn test_lossless_round_trip() {
+        let input = "// header\n`timescale 1ns/1ps\nmodule m ( input a , /* b */ output y ) ;\r\n\
+            \tassign y = ~a; // invert\n\
+            endmodule\n\n";
+        let (db, file, tokens) = lexed("m.sv", input);
+        let root = parse_tokens_with_spans(db.file(file), &tokens).unwrap().syntax();
+        assert_eq!(root.to_string(), input);
+        assert_eq!(usize::from(root.text_range().len()), input.len());
+
+        // Leading trivia stays outside of the node that follows it
+        let module = SourceFile::cast(root.clone()).unwrap().modules().next().unwrap();
+        assert!(module.syntax().to_string().starts_with("module m"));
+        let assign = module.continuous_assigns().next().unwrap();
+        assert_eq!(assign.syntax().to_string(), "assign y = ~a;");
+        assert_eq!(&input[assign.syntax().text_range()], "assign y = ~a;");
+        assert_eq!(assign.syntax().parent().unwrap().kind(), SyntaxKind::ModuleDeclaration);
+    }
+
+    #[test]
+    fn test_filtered_tokens_are_kept_as_skipped_text() {
+        let input = "module m; wire a; endmodule";
+        let (db, file, mut tokens) = lexed("m.sv", input);
+        tokens.drain(3..6);
+        let cst = parse_tokens_with_spans(db.file(file), &tokens).unwrap();
+        assert_eq!(cst.syntax().to_string(), input);
+        let skipped: Vec<_> = cst.syntax().descendants_with_tokens()
+            .filter(|element| element.kind() == SyntaxKind::Skipped)
+            .map(|element| element.to_string())
+            .collect();
+        assert_eq!(skipped, ["wire", "a;"]);
+    }
+
+    #[test]
+    fn test_all_syntax_errors_are_reported() {
+        let input = "module m(input a);\n\
+            wire w\n\
+            assign = a;\n\
+            always_ff @(posedge clk) begin q <= ; ) end\n\
+            endmodule\n\
+            module n; 42 assign x = y; endmodule";
+        let (db, file, tokens) = lexed("m.sv", input);
+        let parse = parse_tokens(db.file(file), &tokens);
+        assert_eq!(parse.cst.syntax().to_string(), input);
+
+        let errors: Vec<String> = parse.errors.iter().map(ToString::to_string).collect();
+        assert_eq!(errors, [
+            "3:1: expected Semicolon, found AssignKeyword 'assign'",
+            "3:8: expected an expression, found Assign '='",
+            "4:37: expected an expression, found Semicolon ';'",
+            "4:39: expected a statement, found RightParen ')'",
+            "6:11: expected a module item, found UnsizedNumber '42'",
+        ]);
+        let first = &parse.errors[0];
+        assert_eq!(first.expected, Expected::Token(SyntaxKind::Semicolon));
+        assert_eq!(first.found, Some((SyntaxKind::AssignKeyword, "assign".to_string())));
+        assert_eq!(first.span.file, file);
+        assert_eq!(db.span_text(&first.span), "assign");
+
+        // Skipped tokens are kept in Error nodes; the next item parses normally
+        let skipped: Vec<String> = parse.cst.syntax().descendants()
+            .filter(|node| node.kind() == SyntaxKind::Error)
+            .map(|node| node.to_string())
+            .collect();
+        assert_eq!(skipped, [")", "42"]);
+        let second = SourceFile::cast(parse.cst.syntax()).unwrap().modules().nth(1).unwrap();
+        assert_eq!(second.continuous_assigns().count(), 1);
+    }
+
+    #[test]
+    fn test_malformed_struct_members_are_skipped() {
+        let input = "module m; struct { 1 } s; typedef struct packed { logic a; ) } t; endmodule";
+        let (db, file, tokens) = lexed("m.sv", input);
+        let parse = parse_tokens(db.file(file), &tokens);
+        assert_eq!(parse.cst.syntax().to_string(), input);
+
+        let errors: Vec<String> = parse.errors.iter().map(ToString::to_string).collect();
+        assert_eq!(errors, [
+            "1:20: expected a struct member, found UnsizedNumber '1'",
+            "1:60: expected a struct member, found RightParen ')'",
+        ]);
+        let skipped: Vec<String> = parse.cst.syntax().descendants()
+            .filter(|node| node.kind() == SyntaxKind::Error)
+            .map(|node| node.to_string())
+            .collect();
+        assert_eq!(skipped, ["1", ")"]);
+    }
+
+    #[test]
+    fn test_missing_statement_leaves_closing_keyword() {
+        let input = "module m;\n\
+            always begin if (a) end\n\
+            initial case (s) 1: endcase\n\
+            initial fork #1 join\n\
+            initial begin endcase x = 1; end\n\
+            endmodule";
+        let (db, file, tokens) = lexed("m.sv", input);
+        let parse = parse_tokens(db.file(file), &tokens);
+        assert_eq!(parse.cst.syntax().to_string(), input);
+
+        let errors: Vec<String> = parse.errors.iter().map(ToString::to_string).collect();
+        assert_eq!(errors, [
+            "2:21: expected a statement, found End 'end'",
+            "3:21: expected a statement, found EndCase 'endcase'",
+            "4:17: expected a statement, found Join 'join'",
+            "5:15: expected a statement, found EndCase 'endcase'",
+        ]);
+        let module = SourceFile::cast(parse.cst.syntax()).unwrap().modules().next().unwrap();
+        let blocks: Vec<String> = module.syntax().descendants()
+            .filter(|node| matches!(node.kind(), SyntaxKind::SeqBlock | SyntaxKind::CaseStatement | SyntaxKind::ParBlock))
+            .map(|node| node.to_string())
+            .collect();
+        assert_eq!(blocks, ["begin if (a) end", "case (s) 1: endcase", "fork #1 join", "begin endcase x = 1; end"]);
+    }
+
+    /// Parse `input` without errors and render the nodes of `kind` it contains
+    fn nodes_of_kind(input: &str, kind: SyntaxKind) -> Vec<String> {
+        let (db, file, tokens) = lexed("test.sv", input);
+        let cst = parse_tokens_with_spans(db.file(file), &tokens).unwrap();
+        assert_eq!(cst.syntax().to_string(), input);
+        cst.syntax().descendants()
+            .filter(|node| node.kind() == kind)
+            .map(|node| node.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_modport_declaration() {
+        let input = "interface bus_if;\n\
+            logic [7:0] data; logic valid, ready;\n\
+            modport master(output data, valid, input ready, import send),\n\
+            \x20       slave(input data, valid, output ready);\n\
+            modport monitor(input .low(data[3:0]), clocking cb);\n\
+            endinterface";
+        assert_eq!(nodes_of_kind(input, SyntaxKind::ModportItem), [
+            "master(output data, valid, input ready, import send)",
+            "slave(input data, valid, output ready)",
+            "monitor(input .low(data[3:0]), clocking cb)",
+        ]);
+        assert_eq!(nodes_of_kind(input, SyntaxKind::ModportPortsDeclaration)[..3], [
+            "output data, valid", "input ready", "import send",
+        ]);
+    }
+
+    #[test]
+    fn test_loop_generate_construct() {
+        let input = "module m #(parameter N = 4) (input [N-1:0] a, output [N-1:0] y);\n\
+            genvar i;\n\
+            for (i = 0; i < N; i++) begin : g\n\
+            \x20   assign y[i] = ~a[i];\n\
+            end\n\
+            for (genvar j = 0; j < N; j = j + 1) always_comb z[j] = a[j];\n\
+            endmodule";
+        assert_eq!(nodes_of_kind(input, SyntaxKind::LoopGenerateConstruct).len(), 2);
+        assert_eq!(nodes_of_kind(input, SyntaxKind::GenvarInitialization), ["i = 0", "genvar j = 0"]);
+        assert_eq!(nodes_of_kind(input, SyntaxKind::GenerateBlock), ["begin : g\n    assign y[i] = ~a[i];\nend"]);
+    }
+
+    #[test]
+    fn test_if_generate_construct() {
+        let input = "module m #(parameter N = 4) (input [N-1:0] a, output y);\n\
+            if (N > 2) begin : wide\n\
+            \x20   assign y = &a;\n\
+            end else if (N == 2) assign y = a[0] & a[1];\n\
+            else begin\n\
+            \x20   assign y = a[0];\n\
+            end\n\
+            endmodule";
+        assert_eq!(nodes_of_kind(input, SyntaxKind::IfGenerateConstruct).len(), 2);
+        assert_eq!(nodes_of_kind(input, SyntaxKind::GenerateBlock).len(), 2);
+        assert_eq!(nodes_of_kind(input, SyntaxKind::ContinuousAssign).len(), 3);
+    }
+
+    #[test]
+    fn test_case_generate_construct() {
+        let input = "module m #(parameter MODE = 0) (input a, b, output y);\n\
+            case (MODE)\n\
+            \x20   0, 1: assign y = a & b;\n\
+            \x20   2: begin : or_gate assign y = a | b; end\n\
+            \x20   default assign y = a ^ b;\n\
+            endcase\n\
+            endmodule";
+        assert_eq!(nodes_of_kind(input, SyntaxKind::CaseGenerateConstruct).len(), 1);
+        assert_eq!(nodes_of_kind(input, SyntaxKind::CaseGenerateItem), [
+            "0, 1: assign y = a & b;",
+            "2: begin : or_gate assign y = a | b; end",
+            "default assign y = a ^ b;",
+        ]);
+    }
+
+    #[test]
+    fn test_one_error_per_malformed_statement() {
+        // Constructs failing at the token that already caused an error are not reported again
+        let errors = |input: &str| -> Vec<String> {
+            let (db, file, tokens) = lexed("m.sv", input);
+            parse_tokens(db.file(file), &tokens).errors.iter().map(ToString::to_string).collect()
+        };
+        assert_eq!(
+            errors("module m;\ninitial begin\n    x = (a b;\nend\nendmodule"),
+            ["3:12: expected RightParen, found Identifier 'b'"]
+        );
+        assert_eq!(
+            errors("interface i;\nmodport mp(output data valid);\nendinterface"),
+            ["2:24: expected RightParen, found Identifier 'valid'"]
+        );
+    }
+
+    #[test]
+    fn test_missing_end_does_not_swallow_endmodule() {
+        let input = "module m; initial begin x = 1; endmodule";
+        let (db, file, tokens) = lexed("m.sv", input);
+        let errors = parse_tokens_with_spans(db.file(file), &tokens).unwrap_err();
+        assert_eq!(errors.to_string(), "1:32: expected End, found EndModule 'endmodule'");
+    }
+}
@@ -0,0 +1,416 @@
+//! Token cursor and tree builder shared by the grammar functions.
+//!
+//! Grammar functions look at the current token, open nodes with
+//! [`start_node`](Parser::start_node), consume tokens with [`bump`](Parser::bump)
+//! and close nodes with [`finish_node`](Parser::finish_node). Left-recursive
+//! constructs such as binary expressions take a [`Checkpoint`] before parsing the
+//! left operand and wrap it afterwards with [`start_node_at`](Parser::start_node_at).
+//!
+//! The lexer drops whitespace and comments, so the builder re-creates them from
+//! the source text between token spans. Trivia goes into the innermost node that
+//! is open when the next token or node starts, which keeps it out of the node
+//! that follows; trivia after the last token goes into the root.
+//!
+//! Tokens are pulled from the token iterator only as far as the grammar looks
+//! ahead, so a lexer can feed the parser without the file's tokens being
+//! collected first.
+
+use crate::error::{Expected, ParseError};
+use crate::SyntaxKind;
+use rowan::{Checkpoint, GreenNode, GreenNodeBuilder, TextSize};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::ops::Range;
+use svcs_lexer::utils::LineIndex;
+use svcs_lexer::{Interner, SpannedToken, Symbol, Token};
+use svcs_source::{FileId, SourceFile, Span};
+
+/// Synchronization points for error recovery: the end of a statement or item
+/// and the keywords closing a block or design unit
+const SYNC_TOKENS: &[Token] = &[
+    Token::Semicolon, Token::End, Token::EndModule, Token::EndInterface, Token::EndProgram,
+    Token::EndPackage, Token::EndFunction, Token::EndTask, Token::EndCase, Token::EndGenerate,
+    Token::EndClass, Token::EndClocking, Token::Join, Token::JoinAny, Token::JoinNone,
+];
+
+/// Keywords closing a design unit, subroutine or generate region
+const UNIT_END_TOKENS: &[Token] = &[
+    Token::EndModule, Token::EndInterface, Token::EndProgram, Token::EndPackage,
+    Token::EndFunction, Token::EndTask, Token::EndGenerate, Token::EndClass,
+];
+
+/// Delimiters an enclosing construct is waiting for
+const CLOSING_TOKENS: &[Token] = &[
+    Token::RightParen, Token::RightBracket, Token::RightBrace, Token::Comma, Token::Colon,
+    Token::Assign,
+];
+
+/// Lookaheads allowed without consuming a token. A grammar loop that stops
+/// making progress panics here instead of hanging.
+const STEP_LIMIT: u32 = 1_000_000;
+
+/// Token iterator with a buffer for the tokens looked at but not consumed yet
+struct TokenStream<'a> {
+    tokens: Box<dyn Iterator<Item = SpannedToken> + 'a>,
+    lookahead: VecDeque<SpannedToken>,
+}
+
+impl TokenStream<'_> {
+    fn peek_nth(&mut self, n: usize) -> Option<&SpannedToken> {
+        while self.lookahead.len() <= n {
+            let token = self.tokens.next()?;
+            self.lookahead.push_back(token);
+        }
+        self.lookahead.get(n)
+    }
+
+    fn next(&mut self) -> Option<SpannedToken> {
+        self.lookahead.pop_front().or_else(|| self.tokens.next())
+    }
+}
+
+pub(crate) struct Parser<'a> {
+    file: FileId,
+    input: &'a str,
+    tokens: RefCell<TokenStream<'a>>,
+    /// Span of the last consumed token
+    previous: Option<Range<usize>>,
+    /// Number of consumed tokens
+    pos: usize,
+    /// Lookaheads since the last consumed token
+    steps: Cell<u32>,
+    line_index: LineIndex,
+    builder: GreenNodeBuilder<'static>,
+    /// Number of started and not yet finished nodes
+    depth: usize,
+    /// End of the source text already added to the tree
+    offset: usize,
+    errors: Vec<ParseError>,
+    /// Interns identifier tokens as they are added to the tree
+    interner: Option<&'a Interner>,
+    /// Symbols of the identifier tokens added so far, in source order
+    symbols: Vec<(TextSize, Symbol)>,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(source: &'a SourceFile, tokens: impl IntoIterator<Item = SpannedToken> + 'a) -> Self {
+        let input = source.text();
+        Self {
+            file: source.id(),
+            input,
+            tokens: RefCell::new(TokenStream { tokens: Box::new(tokens.into_iter()), lookahead: VecDeque::new() }),
+            previous: None,
+            pos: 0,
+            steps: Cell::new(0),
+            line_index: LineIndex::new(input),
+            builder: GreenNodeBuilder::new(),
+            depth: 0,
+            offset: 0,
+            errors: Vec::new(),
+            interner: None,
+            symbols: Vec::new(),
+        }
+    }
+
+    pub fn with_interner(mut self, interner: &'a Interner) -> Self {
+        self.interner = Some(interner);
+        self
+    }
+
+    /// The finished tree, the symbols of its identifier tokens by start offset
+    /// and all syntax errors. Every opened node must have been finished, the
+    /// outermost one becoming the root.
+    pub fn finish(self) -> (GreenNode, Vec<(TextSize, Symbol)>, Vec<ParseError>) {
+        assert_eq!(self.depth, 0, "unbalanced start_node/finish_node");
+        (self.builder.finish(), self.symbols, self.errors)
+    }
+
+    // === Cursor ===
+
+    pub fn current(&self) -> Option<Token> {
+        self.nth(0)
+    }
+
+    pub fn nth(&self, n: usize) -> Option<Token> {
+        let steps = self.steps.get();
+        assert!(steps < STEP_LIMIT, "parser stuck at token {}: {:?}", self.pos, self.peek(0));
+        self.steps.set(steps + 1);
+        self.peek(n).map(|(token, _)| token)
+    }
+
+    /// The n-th token ahead and its span, without counting as a lookahead step
+    fn peek(&self, n: usize) -> Option<(Token, Range<usize>)> {
+        self.tokens.borrow_mut().peek_nth(n).cloned()
+    }
+
+    pub fn at(&self, token: Token) -> bool {
+        self.current() == Some(token)
+    }
+
+    pub fn nth_at(&self, n: usize, token: Token) -> bool {
+        self.nth(n) == Some(token)
+    }
+
+    pub fn at_any(&self, tokens: &[Token]) -> bool {
+        self.current().is_some_and(|token| tokens.contains(&token))
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.peek(0).is_none()
+    }
+
+    /// Whether the current token closes a design unit, subroutine or generate
+    /// region. Block bodies stop there, so a missing `end` does not swallow it.
+    pub fn at_unit_end(&self) -> bool {
+        self.at_any(UNIT_END_TOKENS)
+    }
+
+    /// At a keyword closing a block or design unit, which error recovery leaves
+    /// for the enclosing construct to consume
+    pub fn at_block_end(&self) -> bool {
+        !self.at(Token::Semicolon) && self.at_any(SYNC_TOKENS)
+    }
+
+    /// Source text of the n-th token ahead, empty past the end
+    pub fn nth_text(&self, n: usize) -> &'a str {
+        self.peek(n).map_or("", |(_, span)| &self.input[span])
+    }
+
+    /// Whether a line break separates the current token from the previous one
+    pub fn at_line_start(&self) -> bool {
+        match (&self.previous, self.peek(0)) {
+            (Some(previous), Some((_, current))) => self.input[previous.end..current.start].contains('\n'),
+            _ => true,
+        }
+    }
+
+    /// Whether the current token directly follows the previous one, without trivia
+    pub fn joined(&self) -> bool {
+        match (&self.previous, self.peek(0)) {
+            (Some(previous), Some((_, current))) => previous.end == current.start,
+            _ => false,
+        }
+    }
+
+    // === Tree building ===
+
+    pub fn start_node(&mut self, kind: SyntaxKind) {
+        self.attach_trivia();
+        self.builder.start_node(kind.into());
+        self.depth += 1;
+    }
+
+    pub fn checkpoint(&mut self) -> Checkpoint {
+        self.attach_trivia();
+        self.builder.checkpoint()
+    }
+
+    /// Open a node that adopts every child added since `checkpoint`
+    pub fn start_node_at(&mut self, checkpoint: Checkpoint, kind: SyntaxKind) {
+        self.builder.start_node_at(checkpoint, kind.into());
+        self.depth += 1;
+    }
+
+    pub fn finish_node(&mut self) {
+        if self.depth == 1 {
+            self.add_trivia(self.input.len());
+        }
+        self.builder.finish_node();
+        self.depth -= 1;
+    }
+
+    /// Add the current token to the innermost open node
+    pub fn bump(&mut self) {
+        let Some((token, span)) = self.tokens.get_mut().next() else {
+            return;
+        };
+        self.add_trivia(span.start);
+        let text = &self.input[span.clone()];
+        if let Some(symbol) = self.interner.and_then(|interner| interner.intern_token(&token, text)) {
+            self.symbols.push((TextSize::from(span.start as u32), symbol));
+        }
+        self.builder.token(SyntaxKind::from(&token).into(), text);
+        self.offset = span.end;
+        self.previous = Some(span);
+        self.pos += 1;
+        self.steps.set(0);
+    }
+
+    /// Add the trivia before the current token to the innermost open node
+    fn attach_trivia(&mut self) {
+        if self.depth > 0 {
+            let end = self.peek(0).map_or(self.input.len(), |(_, span)| span.start);
+            self.add_trivia(end);
+        }
+    }
+
+    fn add_trivia(&mut self, end: usize) {
+        if end <= self.offset {
+            return;
+        }
+        for (kind, text) in split_trivia(&self.input[self.offset..end]) {
+            self.builder.token(kind.into(), text);
+        }
+        self.offset = end;
+    }
+
+    pub fn eat(&mut self, token: Token) -> bool {
+        if self.at(token) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume `token` or report it as missing
+    pub fn expect(&mut self, token: Token) -> bool {
+        if self.eat(token.clone()) {
+            return true;
+        }
+        self.report(Expected::Token(SyntaxKind::from(&token)));
+        false
+    }
+
+    /// Report that `expected` (a construct such as "an expression") is missing
+    /// at the current token
+    pub fn error(&mut self, expected: &'static str) {
+        self.report(Expected::Construct(expected));
+    }
+
+    fn report(&mut self, expected: Expected) {
+        let (found, span) = match self.peek(0) {
+            Some((token, span)) => (Some((SyntaxKind::from(&token), self.input[span.clone()].to_string())), span),
+            None => (None, self.input.len()..self.input.len()),
+        };
+        // Without progress since the last error, this one is a cascade of it
+        if self.errors.last().is_some_and(|last| last.span.range.start == span.start) {
+            return;
+        }
+        let (line, column) = self.line_index.line_col(self.input, span.start);
+        self.errors.push(ParseError { expected, found, span: Span::new(self.file, span), line, column });
+    }
+
+    /// Report an error and wrap the current token in an `Error` node, unless it
+    /// closes an enclosing construct and is left for that to consume
+    pub fn error_and_bump(&mut self, expected: &'static str) {
+        self.error(expected);
+        if !self.at_end() && !self.at_any(SYNC_TOKENS) && !self.at_any(CLOSING_TOKENS) {
+            self.start_node(SyntaxKind::Error);
+            self.bump();
+            self.finish_node();
+        }
+    }
+
+    /// Report an error and skip to the next synchronization point: a token of
+    /// `recovery` (such as the keywords starting the next item) or of the
+    /// synchronization set. Skipped tokens, at least one, go into an `Error` node;
+    /// a `;` ends the skipped construct and is skipped with it.
+    pub fn error_and_recover(&mut self, expected: &'static str, recovery: &[Token]) {
+        self.error(expected);
+        if self.at_end() {
+            return;
+        }
+        self.start_node(SyntaxKind::Error);
+        let first = self.current();
+        self.bump();
+        if first != Some(Token::Semicolon) {
+            while !self.at_end() && !self.at_any(SYNC_TOKENS) && !self.at_any(recovery) {
+                self.bump();
+            }
+            self.eat(Token::Semicolon);
+        }
+        self.finish_node();
+    }
+}
+
+/// Split the text between two tokens into whitespace, comments and skipped text
+fn split_trivia(text: &str) -> Vec<(SyntaxKind, &str)> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let (kind, len) = if rest.starts_with(char::is_whitespace) {
+            (SyntaxKind::Whitespace, rest.find(|c: char| !c.is_whitespace()).unwrap_or(rest.len()))
+        } else if rest.starts_with("//") {
+            (SyntaxKind::Comment, rest.find('\n').unwrap_or(rest.len()))
+        } else if let Some(body) = rest.strip_prefix("/*") {
+            (SyntaxKind::Comment, body.find("*/").map_or(rest.len(), |end| end + 4))
+        } else {
+            let end = rest
+                .char_indices()
+                .find(|&(i, c)| c.is_whitespace() || rest[i..].starts_with("//") || rest[i..].starts_with("/*"))
+                .map_or(rest.len(), |(i, _)| i);
+            (SyntaxKind::Skipped, end)
+        };
+        pieces.push((kind, &rest[..len]));
+        rest = &rest[len..];
+    }
+    pieces
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use super::*;
+    use crate::SyntaxNode;
+    use svcs_lexer::create_default_lexer;
+    use svcs_source::SourceDb;
+
+    /// Compact `(Kind child ...)` rendering with tokens as their text, trivia omitted
+    pub fn sexp(node: &SyntaxNode) -> String {
+        match children(node) {
+            rendered if rendered.is_empty() => format!("({})", node.kind()),
+            rendered => format!("({} {})", node.kind(), rendered),
+        }
+    }
+
+    fn children(node: &SyntaxNode) -> String {
+        let rendered: Vec<String> = node
+            .children_with_tokens()
+            .filter_map(|child| match child {
+                rowan::NodeOrToken::Node(node) => Some(sexp(&node)),
+                rowan::NodeOrToken::Token(token) => (!token.kind().is_trivia()).then(|| token.text().to_string()),
+            })
+            .collect();
+        rendered.join(" ")
+    }
+
+    /// Run one grammar function over `input` and render what it built. Panics on
+    /// syntax errors, unconsumed tokens or a tree that does not reproduce `input`.
+    pub fn parse_with(input: &str, production: fn(&mut Parser)) -> String {
+        let tokens = create_default_lexer(input, "test.sv".to_string()).unwrap().tokenize().unwrap();
+        let mut db = SourceDb::new();
+        let file = db.add_file("test.sv", input);
+        let mut parser = Parser::new(db.file(file), tokens);
+        parser.start_node(SyntaxKind::SourceFile);
+        production(&mut parser);
+        assert!(parser.at_end(), "unconsumed input at token {}: {:?}", parser.pos, parser.current());
+        parser.finish_node();
+        let (green, _, errors) = parser.finish();
+        assert!(errors.is_empty(), "{:?}", errors);
+        let root = SyntaxNode::new_root(green);
+        assert_eq!(root.to_string(), input);
+        children(&root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_trivia() {
+        assert_eq!(
+            split_trivia(" // a\n\t/* b */x y/*"),
+            [
+                (SyntaxKind::Whitespace, " "),
+                (SyntaxKind::Comment, "// a"),
+                (SyntaxKind::Whitespace, "\n\t"),
+                (SyntaxKind::Comment, "/* b */"),
+                (SyntaxKind::Skipped, "x"),
+                (SyntaxKind::Whitespace, " "),
+                (SyntaxKind::Skipped, "y"),
+                (SyntaxKind::Comment, "/*"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! Kinds of every token and node in the syntax tree.
+//!
+//! Token kinds mirror the lexer's [`Token`] variants one to one, followed by the
+//! trivia kinds the parser fills the gaps between tokens with; node kinds are
+//! named after the grammar productions that build them.
+
+use std::fmt;
+use svcs_lexer::Token;
+
+macro_rules! syntax_kinds {
+    (tokens { $($token:ident),* $(,)? } nodes { $($node:ident),* $(,)? }) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(u16)]
+        pub enum SyntaxKind {
+            $($token,)*
+            /// Spaces, tabs and line breaks
+            Whitespace,
+            /// Line or block comment
+            Comment,
+            /// Source text whose tokens were filtered out before parsing, such as
+            /// a `translate_off` region in synthesis mode
+            Skipped,
+            /// A token the lexer could not recognize
+            ErrorToken,
+            $($node,)*
+        }
+
+        /// Every kind, indexed by its discriminant
+        const KINDS: &[SyntaxKind] = &[
+            $(SyntaxKind::$token,)*
+            SyntaxKind::Whitespace,
+            SyntaxKind::Comment,
+            SyntaxKind::Skipped,
+            SyntaxKind::ErrorToken,
+            $(SyntaxKind::$node,)*
+        ];
+
+        impl From<&Token> for SyntaxKind {
+            fn from(token: &Token) -> Self {
+                match token {
+                    $(Token::$token => SyntaxKind::$token,)*
+                    Token::Error => SyntaxKind::ErrorToken,
+                }
+            }
+        }
+    };
+}
+
+syntax_kinds! {
+    tokens {
+        Module, EndModule, Package, EndPackage, Program, EndProgram, Import, Export, Generate,
+        EndGenerate, GenVar, Function, EndFunction, Task, EndTask, Class, EndClass, Interface,
+        EndInterface, ModPort, Clocking, EndClocking, Virtual, Logic, Bit, Byte, Int, Integer,
+        Real, String, ShortInt, LongInt, Time, ShortReal, RealTime, Chandle, Event, Wire, Reg,
+        Tri, TriAnd, TriOr, Tri0, Tri1, TriReg, WAnd, WOr, Supply0, Supply1, UWire, Vectored,
+        Scalared, Strong0, Strong1, Pull0, Pull1, Weak0, Weak1, HighZ0, HighZ1, Small, Medium,
+        Large, Input, Output, Inout, Ref, Var, Signed, Unsigned, Packed, Struct, Union, Enum,
+        Typedef, Parameter, LocalParam, Type, Const, Static, Automatic, Always, AlwaysComb,
+        AlwaysFF, AlwaysLatch, Initial, Final, PosEdge, NegEdge, Edge, Or, Iff, If, Else,
+        Unique, Unique0, Priority, Case, CaseX, CaseZ, EndCase, Default, For, ForEach, While,
+        Do, Repeat, Forever, Begin, End, Fork, Join, JoinAny, JoinNone, Wait, Disable, Break,
+        Continue, Return, Plus, Minus, Multiply, Divide, Modulo, Power, Equal, NotEqual,
+        CaseEqual, CaseNotEqual, LessThan, GreaterThan, LessEqual, GreaterEqual, LogicalAnd,
+        LogicalOr, LogicalNot, BitwiseAnd, BitwiseOr, BitwiseXor, BitwiseNot, ReductionNand,
+        ReductionNor, BitwiseXnor, WildcardEqual, WildcardNotEqual, Implication, Equivalence,
+        IndexedPlus, IndexedMinus, Inside, LeftShift, RightShift, ArithmeticLeftShift,
+        ArithmeticRightShift, Assign, PlusAssign, MinusAssign, MultiplyAssign, DivideAssign,
+        ModuloAssign, AndAssign, OrAssign, XorAssign, LeftShiftAssign, RightShiftAssign,
+        Increment, Decrement, AssignKeyword, Identifier, SystemIdentifier, SizedNumber,
+        UnbasedUnsizedLiteral, UnsizedNumber, RealNumber, StringLiteral, LeftParen, RightParen,
+        LeftBracket, RightBracket, LeftBrace, RightBrace, Dot, Comma, Semicolon, Colon, Scope,
+        Question, Hash, At, Apostrophe, Dollar, PreprocessorDirective, ProtectedEnvelope,
+        CustomKeyword,
+    }
+    nodes {
+        SourceFile, ModuleDeclaration, InterfaceDeclaration, ProgramDeclaration,
+        PackageDeclaration, PackageImportDeclaration, PackageImportItem, Directive, EndLabel,
+        EmptyItem, PortList, Port, PortDeclaration, DataType, PackedDimension,
+        UnpackedDimension, NetDeclaration, DataDeclaration, Declarator, ParameterDeclaration,
+        GenvarDeclaration, FunctionDeclaration, TaskDeclaration, ContinuousAssign,
+        NetAssignment, AlwaysConstruct, InitialConstruct, FinalConstruct, GenerateRegion,
+        ModuleInstantiation, HierarchicalInstance, PortConnectionList, NamedPortConnection,
+        OrderedPortConnection, SeqBlock, NullStatement, IfStatement, CaseStatement, CaseItem,
+        ForStatement, ForInitialization, ForVariableDeclaration, ForStep, VariableAssignment,
+        WhileStatement, RepeatStatement, ForeverStatement, TimingControlStatement, DelayControl,
+        EventControl, EventExpression, BlockingAssignment, NonblockingAssignment,
+        SubroutineCallStatement, ReturnStatement, JumpStatement, Literal, NameRef,
+        ParenExpression, UnaryExpression, BinaryExpression, ConditionalExpression,
+        InsideExpression, ValueRange, IncDecExpression, IndexExpression, RangeSelect,
+        IndexedPartSelect, MemberAccess, CallExpression, ArgumentList, CastExpression,
+        Concatenation, Replication, StreamingConcatenation, AssignmentPattern, PatternKeyValue,
+        InterfacePortHeader, ParameterPortList, ParameterPortDeclaration, TypeAssignment,
+        ParameterValueAssignment, NamedParameterAssignment, OrderedParameterAssignment,
+        ImplicitPortConnection, WildcardPortConnection, LabeledStatement, ParBlock,
+        ForeachStatement, LoopVariables, DoWhileStatement, WaitStatement, DisableStatement,
+        EventTrigger, ExpressionStatement, StructUnionType, StructMember, EnumType, EnumMember,
+        TypedefDeclaration, DriveStrength, ChargeStrength, Delay3, ModportDeclaration,
+        ModportItem, ModportPortsDeclaration, ModportPort, LoopGenerateConstruct,
+        GenvarInitialization, IfGenerateConstruct, CaseGenerateConstruct, CaseGenerateItem,
+        GenerateBlock, TypeReference, Error,
+    }
+}
+
+impl SyntaxKind {
+    /// Whether this kind labels a leaf holding a single token
+    pub fn is_token(self) -> bool {
+        self <= SyntaxKind::ErrorToken
+    }
+
+    pub fn is_node(self) -> bool {
+        !self.is_token()
+    }
+
+    pub fn is_trivia(self) -> bool {
+        matches!(self, SyntaxKind::Whitespace | SyntaxKind::Comment | SyntaxKind::Skipped)
+    }
+
+    /// Inverse of `kind as u16`
+    pub fn from_raw(raw: u16) -> Self {
+        KINDS[raw as usize]
+    }
+}
+
+impl fmt::Display for SyntaxKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_kinds() {
+        assert_eq!(SyntaxKind::from(&Token::EndModule), SyntaxKind::EndModule);
+        assert_eq!(SyntaxKind::from(&Token::Error), SyntaxKind::ErrorToken);
+        assert!(SyntaxKind::Identifier.is_token());
+        assert!(SyntaxKind::SourceFile.is_node() && SyntaxKind::Error.is_node());
+        assert_eq!(SyntaxKind::ModuleDeclaration.to_string(), "ModuleDeclaration");
+        assert!(SyntaxKind::Comment.is_token() && SyntaxKind::Comment.is_trivia());
+        for kind in [SyntaxKind::Module, SyntaxKind::Skipped, SyntaxKind::Error] {
+            assert_eq!(SyntaxKind::from_raw(kind as u16), kind);
+        }
+    }
+}
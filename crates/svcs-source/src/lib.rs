@@ -0,0 +1,362 @@
+//! Source file database shared by every SVCS stage.
+//!
+//! A [`SourceDb`] reads each file once, hands out a stable [`FileId`] for it and
+//! records which files include which. Later stages refer to source text through
+//! [`Span`]s of `(FileId, Range)` instead of paths, so diagnostics, caches and the
+//! language server can all resolve locations against the same database.
+
+use memmap2::Mmap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::ops::{Deref, Range};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tracing::debug;
+
+/// Files at least this large are memory-mapped instead of read into memory
+pub const DEFAULT_MMAP_THRESHOLD: u64 = 1024 * 1024;
+
+#[derive(Error, Debug)]
+pub enum SourceError {
+    #[error("Failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("{path} is not valid UTF-8: {source}")]
+    InvalidUtf8 {
+        path: PathBuf,
+        source: std::str::Utf8Error,
+    },
+}
+
+/// Stable id of a file in a [`SourceDb`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FileId(u32);
+
+impl FileId {
+    pub fn as_u32(self) -> u32 {
+        self.0
+    }
+}
+
+/// Byte range in a specific file
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Span {
+    pub file: FileId,
+    pub range: Range<usize>,
+}
+
+impl Span {
+    pub fn new(file: FileId, range: Range<usize>) -> Self {
+        Self { file, range }
+    }
+}
+
+/// File contents, either owned or mapped from disk. Always valid UTF-8.
+enum SourceText {
+    Owned(String),
+    Mapped(Mmap),
+}
+
+impl Deref for SourceText {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            SourceText::Owned(text) => text,
+            // Checked when the file was mapped
+            SourceText::Mapped(map) => unsafe { std::str::from_utf8_unchecked(map) },
+        }
+    }
+}
+
+pub struct SourceFile {
+    id: FileId,
+    path: PathBuf,
+    text: SourceText,
+}
+
+impl SourceFile {
+    pub fn id(&self) -> FileId {
+        self.id
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.text, SourceText::Mapped(_))
+    }
+
+    /// Span of `range` in this file
+    pub fn span(&self, range: Range<usize>) -> Span {
+        Span::new(self.id, range)
+    }
+}
+
+impl fmt::Debug for SourceFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SourceFile")
+            .field("id", &self.id)
+            .field("path", &self.path)
+            .field("len", &self.text.len())
+            .field("mapped", &self.is_mapped())
+            .finish()
+    }
+}
+
+pub struct SourceDb {
+    files: Vec<SourceFile>,
+    /// Canonical path to id, so a file reached through several paths loads once
+    by_path: HashMap<PathBuf, FileId>,
+    /// Files directly included by each file, in include order
+    includes: HashMap<FileId, Vec<FileId>>,
+    mmap_threshold: u64,
+}
+
+impl Default for SourceDb {
+    fn default() -> Self {
+        Self {
+            files: Vec::new(),
+            by_path: HashMap::new(),
+            includes: HashMap::new(),
+            mmap_threshold: DEFAULT_MMAP_THRESHOLD,
+        }
+    }
+}
+
+impl SourceDb {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_mmap_threshold(mut self, bytes: u64) -> Self {
+        self.mmap_threshold = bytes;
+        self
+    }
+
+    /// Load a file from disk, or return its id if it is already loaded.
+    ///
+    /// Large files are memory-mapped; they must not be modified while the
+    /// database is alive.
+    pub fn load(&mut self, path: &Path) -> Result<FileId, SourceError> {
+        let key = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        if let Some(&id) = self.by_path.get(&key) {
+            return Ok(id);
+        }
+
+        let io_error = |source| SourceError::Io { path: path.to_path_buf(), source };
+        let len = fs::metadata(path).map_err(io_error)?.len();
+        let text = if len >= self.mmap_threshold && len > 0 {
+            let file = fs::File::open(path).map_err(io_error)?;
+            // SAFETY: the map is read-only and callers are told not to modify
+            // loaded files
+            let map = unsafe { Mmap::map(&file) }.map_err(io_error)?;
+            std::str::from_utf8(&map)
+                .map_err(|source| SourceError::InvalidUtf8 { path: path.to_path_buf(), source })?;
+            SourceText::Mapped(map)
+        } else {
+            let bytes = fs::read(path).map_err(io_error)?;
+            let text = String::from_utf8(bytes).map_err(|e| SourceError::InvalidUtf8 {
+                path: path.to_path_buf(),
+                source: e.utf8_error(),
+            })?;
+            SourceText::Owned(text)
+        };
+
+        let id = self.push(path.to_path_buf(), text);
+        self.by_path.insert(key, id);
+        debug!("Loaded {} ({} bytes) as {:?}", path.display(), len, id);
+        Ok(id)
+    }
+
+    /// Add an in-memory file, e.g. an unsaved editor buffer. Always creates a new id.
+    pub fn add_file(&mut self, path: impl Into<PathBuf>, text: impl Into<String>) -> FileId {
+        self.push(path.into(), SourceText::Owned(text.into()))
+    }
+
+    fn push(&mut self, path: PathBuf, text: SourceText) -> FileId {
+        let id = FileId(self.files.len() as u32);
+        self.files.push(SourceFile { id, path, text });
+        id
+    }
+
+    /// Panics if `id` came from another database
+    pub fn file(&self, id: FileId) -> &SourceFile {
+        &self.files[id.0 as usize]
+    }
+
+    pub fn path(&self, id: FileId) -> &Path {
+        self.file(id).path()
+    }
+
+    pub fn text(&self, id: FileId) -> &str {
+        self.file(id).text()
+    }
+
+    /// Source text covered by a span
+    pub fn span_text(&self, span: &Span) -> &str {
+        &self.text(span.file)[span.range.clone()]
+    }
+
+    /// Id of a loaded file
+    pub fn file_id(&self, path: &Path) -> Option<FileId> {
+        let key = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        self.by_path.get(&key).copied()
+    }
+
+    /// All files in load order
+    pub fn files(&self) -> &[SourceFile] {
+        &self.files
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Record that `includer` includes `included`
+    pub fn add_include(&mut self, includer: FileId, included: FileId) {
+        let list = self.includes.entry(includer).or_default();
+        if !list.contains(&included) {
+            list.push(included);
+        }
+    }
+
+    /// Files directly included by `id`, in include order
+    pub fn includes(&self, id: FileId) -> &[FileId] {
+        self.includes.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Files that directly include `id`, in load order
+    pub fn included_by(&self, id: FileId) -> Vec<FileId> {
+        let mut includers: Vec<FileId> = self.includes.iter()
+            .filter(|(_, included)| included.contains(&id))
+            .map(|(&includer, _)| includer)
+            .collect();
+        includers.sort();
+        includers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fresh scratch directory, removed when dropped
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("svcs-source-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn write(&self, name: &str, contents: impl AsRef<[u8]>) -> PathBuf {
+            let path = self.0.join(name);
+            fs::write(&path, contents).unwrap();
+            path
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_load_dedup_mmap_and_includes() {
+        let dir = TempDir::new("load");
+        let small = dir.write("small.sv", "module m; endmodule\n");
+        let large = dir.write("large.svh", "`define W 8\n".repeat(8));
+
+        let mut db = SourceDb::new().with_mmap_threshold(64);
+        let a = db.load(&small).unwrap();
+        let b = db.load(&large).unwrap();
+        assert_eq!(db.load(&dir.0.join(".").join("small.sv")).unwrap(), a);
+        assert!(!db.file(a).is_mapped());
+        assert!(db.file(b).is_mapped());
+        assert_eq!(db.span_text(&db.file(a).span(7..8)), "m");
+        assert!(db.text(b).starts_with("`define W 8"));
+
+        let buffer = db.add_file("unsaved.sv", "`include \"large.svh\"\n");
+        db.add_include(a, b);
+        db.add_include(buffer, b);
+        db.add_include(a, b);
+        assert_eq!(db.includes(a), &[b]);
+        assert_eq!(db.included_by(b), vec![a, buffer]);
+        assert_eq!(db.file_id(&small), Some(a));
+        assert_eq!(db.len(), 3);
+    }
+
+    #[test]
+    fn test_ids_follow_load_order() {
+        let dir = TempDir::new("order");
+        let paths: Vec<_> = ["c.sv", "a.sv", "b.sv"].iter().map(|name| dir.write(name, *name)).collect();
+        let mut db = SourceDb::new();
+        let ids: Vec<_> = paths.iter().map(|path| db.load(path).unwrap()).collect();
+        assert_eq!(ids.iter().map(|id| id.as_u32()).collect::<Vec<_>>(), [0, 1, 2]);
+        let loaded: Vec<_> = db.files().iter().map(|file| (file.id(), file.path().to_path_buf())).collect();
+        assert_eq!(loaded, ids.into_iter().zip(paths).collect::<Vec<_>>());
+        assert_eq!(db.text(FileId(1)), "a.sv");
+    }
+
+    #[test]
+    fn test_in_memory_files_never_dedup() {
+        let mut db = SourceDb::new();
+        let first = db.add_file("buffer.sv", "module a; endmodule");
+        let second = db.add_file("buffer.sv", "module b; endmodule");
+        assert_ne!(first, second);
+        assert_eq!(db.text(second), "module b; endmodule");
+        assert_eq!(db.file_id(Path::new("buffer.sv")), None);
+        assert_eq!(db.span_text(&Span::new(first, 7..8)), "a");
+    }
+
+    #[test]
+    fn test_include_graph() {
+        let mut db = SourceDb::new();
+        let top = db.add_file("top.sv", "");
+        let pkg = db.add_file("pkg.sv", "");
+        let defs = db.add_file("defs.svh", "");
+        let types = db.add_file("types.svh", "");
+        db.add_include(pkg, types);
+        db.add_include(top, defs);
+        db.add_include(top, types);
+        db.add_include(pkg, defs);
+
+        assert_eq!(db.includes(top), &[defs, types]);
+        assert_eq!(db.includes(pkg), &[types, defs]);
+        assert_eq!(db.includes(defs), &[]);
+        assert_eq!(db.included_by(types), vec![top, pkg]);
+        assert!(db.included_by(top).is_empty());
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let dir = TempDir::new("errors");
+        let bad = dir.write("bad.sv", [0xff, 0xfe]);
+        let mut db = SourceDb::new();
+        assert!(matches!(db.load(&bad), Err(SourceError::InvalidUtf8 { .. })));
+        assert!(matches!(db.load(&dir.0.join("missing.sv")), Err(SourceError::Io { .. })));
+
+        // Invalid UTF-8 is rejected when mapped too, and failed loads get no id
+        let mut mapped = SourceDb::new().with_mmap_threshold(1);
+        assert!(matches!(mapped.load(&bad), Err(SourceError::InvalidUtf8 { .. })));
+        assert!(mapped.is_empty());
+    }
+}
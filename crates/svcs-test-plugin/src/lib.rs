@@ -0,0 +1,14 @@
+//! Minimal shared-library lexer plugin, built and loaded by the tests of
+//! `--lexer-plugin` and `DynamicPlugin::load`.
+
+svcs_lexer::declare_lexer_plugin! {
+    name: "Test Pragma Plugin",
+    version: "0.1.0",
+    description: "Keywords of the dynamic plugin tests",
+    category: "test",
+    priority: 70,
+    keywords: {
+        "test_keep" => "CustomKeyword",
+        "test_async" => "CustomKeyword",
+    }
+}
@@ -0,0 +1,19 @@
+//! Parse the tokens of arbitrary UTF-8 input; the parser must never panic and,
+//! syntax errors or not, the tree must reproduce the input byte for byte.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use svcs_lexer::create_default_lexer;
+use svcs_parser::parse_tokens;
+use svcs_source::SourceDb;
+
+fuzz_target!(|input: &str| {
+    let Ok(tokens) = create_default_lexer(input, "fuzz.sv".to_string()).unwrap().tokenize() else {
+        return;
+    };
+    let mut db = SourceDb::new();
+    let file = db.add_file("fuzz.sv", input);
+    let parse = parse_tokens(db.file(file), &tokens);
+    assert_eq!(parse.cst.syntax().to_string(), input);
+});
@@ -0,0 +1,32 @@
+//! Lex arbitrary UTF-8 and check the span invariants of every token produced
+//! before the first error, plus the token/trivia round trip for fully lexed input.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use svcs_lexer::utils::{check_round_trip, check_spans};
+use svcs_lexer::{create_default_lexer, SpannedToken};
+
+fuzz_target!(|input: &str| {
+    let mut lexer = create_default_lexer(input, "fuzz.sv".to_string()).unwrap();
+    let mut tokens: Vec<SpannedToken> = Vec::new();
+    let mut complete = true;
+    for token in lexer.by_ref() {
+        match token {
+            Ok(token) => tokens.push(token),
+            Err(_) => {
+                complete = false;
+                break;
+            }
+        }
+    }
+
+    if let Err(message) = check_spans(input, &tokens) {
+        panic!("{}", message);
+    }
+    if complete {
+        if let Err(message) = check_round_trip(input, &tokens) {
+            panic!("{}", message);
+        }
+    }
+});